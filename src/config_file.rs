@@ -0,0 +1,465 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use toml_edit::{Document, Item, Table, TableLike};
+
+use crate::dependency::DepKind;
+use crate::{
+    DefaultFeaturesStrategy, ExactPinPolicy, PrereleasePolicy, VersionFormat,
+    VersionResolutionOverride, VersionResolutionStrategy,
+};
+
+/// Name of the optional config file in the workspace root
+pub(crate) const CONFIG_FILE_NAME: &str = "workspace-deps.toml";
+
+/// Defaults read from `[workspace.metadata.workspace-deps]` in the root manifest
+/// and from `workspace-deps.toml`, the latter taking precedence.
+/// Keys are the long CLI flag names; unset settings are `None`.
+/// Example:
+/// ```toml
+/// exclude = ["openssl"]
+/// min-members = 3
+/// version-resolution = "highest"
+/// lints = true
+///
+/// [version-resolution-override]
+/// rustls = "fail"
+/// "openssl*" = "fail"
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProjectConfig {
+    pub process_dependencies: Option<bool>,
+    pub process_dev_dependencies: Option<bool>,
+    pub process_build_dependencies: Option<bool>,
+    pub exclude: Option<Vec<String>>,
+    pub exclude_members: Option<Vec<glob::Pattern>>,
+    pub min_members: Option<usize>,
+    pub version_resolution_strategy: Option<VersionResolutionStrategy>,
+    /// Per-dependency strategies, the first matching one wins
+    pub version_resolution_overrides: Vec<VersionResolutionOverride>,
+    pub prerelease_policy: Option<PrereleasePolicy>,
+    pub exact_pin_policy: Option<ExactPinPolicy>,
+    pub respect_rust_version: Option<bool>,
+    pub registry_index: Option<PathBuf>,
+    pub deny_breaking_changes: Option<bool>,
+    pub partial_consolidation: Option<bool>,
+    pub default_features_strategy: Option<DefaultFeaturesStrategy>,
+    pub version_format: Option<VersionFormat>,
+    pub consolidate_path_dependencies: Option<bool>,
+    pub hoist_features: Option<bool>,
+    pub prune_unused: Option<bool>,
+    pub demote: Option<bool>,
+    pub process_package: Option<bool>,
+    pub process_lints: Option<bool>,
+}
+
+/// A parsed settings source, used to point errors at a file location
+struct Source<'a> {
+    path: &'a Path,
+    raw: &'a str,
+}
+
+impl Source<'_> {
+    fn error(&self, span: Option<Range<usize>>, message: impl std::fmt::Display) -> anyhow::Error {
+        match span {
+            Some(span) => {
+                let before = &self.raw[..span.start];
+                let line = before.matches('\n').count() + 1;
+                let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+                let column = before[line_start..].chars().count() + 1;
+                anyhow::anyhow!("{}:{}:{}: {}", self.path.display(), line, column, message)
+            }
+            None => anyhow::anyhow!("{}: {}", self.path.display(), message),
+        }
+    }
+}
+
+impl ProjectConfig {
+    /// Read the workspace metadata table and `workspace-deps.toml`, if present
+    pub(crate) fn load(root_dir: &Path) -> Result<Self> {
+        let manifest_path = root_dir.join("Cargo.toml");
+        let content = fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+        let metadata = Self::parse(
+            &manifest_path,
+            &content,
+            &["workspace", "metadata", "workspace-deps"],
+        )?;
+
+        let config_path = root_dir.join(CONFIG_FILE_NAME);
+        if !config_path.exists() {
+            return Ok(metadata);
+        }
+        let content = fs::read_to_string(&config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let file = Self::parse(&config_path, &content, &[])?;
+
+        Ok(file.or(metadata))
+    }
+
+    /// Parse the settings table found at `table_path` in `content`
+    fn parse(path: &Path, content: &str, table_path: &[&str]) -> Result<Self> {
+        let doc = Document::parse(content)
+            .with_context(|| format!("Failed to parse TOML at {}", path.display()))?;
+        let source = Source { path, raw: content };
+
+        let mut config = ProjectConfig::default();
+        let Some(table) = find_table(&doc, &source, table_path)? else {
+            return Ok(config);
+        };
+        for (name, item) in table.iter() {
+            let value = Setting::new(&source, table, name, item);
+
+            match name {
+                "no-dependencies" => config.process_dependencies = Some(!value.bool()?),
+                "no-dev-dependencies" => config.process_dev_dependencies = Some(!value.bool()?),
+                "no-build-dependencies" => config.process_build_dependencies = Some(!value.bool()?),
+                "exclude" => config.exclude = Some(value.strings()?),
+                "exclude-members" => {
+                    let patterns = value
+                        .strings()?
+                        .iter()
+                        .map(|pattern| {
+                            glob::Pattern::new(pattern).map_err(|e| {
+                                value.error(format!("Invalid glob pattern '{}': {}", pattern, e))
+                            })
+                        })
+                        .collect::<Result<_>>()?;
+                    config.exclude_members = Some(patterns);
+                }
+                "min-members" => {
+                    let min_members = value.integer()?;
+                    config.min_members = Some(usize::try_from(min_members).map_err(|_| {
+                        value.error(format!(
+                            "'min-members' must not be negative, got {}",
+                            min_members
+                        ))
+                    })?);
+                }
+                // A table keeps the original meaning of per-dependency strategies
+                "version-resolution" if item.is_table_like() => {
+                    config
+                        .version_resolution_overrides
+                        .extend(value.overrides()?);
+                }
+                "version-resolution" => {
+                    config.version_resolution_strategy = Some(value.enum_value()?)
+                }
+                "version-resolution-override" => {
+                    config
+                        .version_resolution_overrides
+                        .extend(value.overrides()?);
+                }
+                "prerelease" => config.prerelease_policy = Some(value.enum_value()?),
+                "exact-pins" => config.exact_pin_policy = Some(value.enum_value()?),
+                "msrv" => config.respect_rust_version = Some(value.bool()?),
+                "registry-index" => {
+                    // Relative to the directory holding the settings
+                    let dir = path.parent().unwrap_or(Path::new("."));
+                    config.registry_index = Some(dir.join(value.string()?));
+                }
+                "deny-breaking" => config.deny_breaking_changes = Some(value.bool()?),
+                "partial" => config.partial_consolidation = Some(value.bool()?),
+                "default-features-resolution" => {
+                    config.default_features_strategy = Some(value.enum_value()?)
+                }
+                "version-format" => config.version_format = Some(value.enum_value()?),
+                "path-dependencies" => config.consolidate_path_dependencies = Some(value.bool()?),
+                "hoist-features" => config.hoist_features = Some(value.bool()?),
+                "prune-unused" => config.prune_unused = Some(value.bool()?),
+                "demote" => config.demote = Some(value.bool()?),
+                "package" => config.process_package = Some(value.bool()?),
+                "lints" => config.process_lints = Some(value.bool()?),
+                _ => return Err(value.unknown_key()),
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Fill settings missing here from `fallback`, e.g. command line flags
+    /// from the settings files
+    pub fn or(self, fallback: Self) -> Self {
+        ProjectConfig {
+            process_dependencies: self.process_dependencies.or(fallback.process_dependencies),
+            process_dev_dependencies: self
+                .process_dev_dependencies
+                .or(fallback.process_dev_dependencies),
+            process_build_dependencies: self
+                .process_build_dependencies
+                .or(fallback.process_build_dependencies),
+            exclude: self.exclude.or(fallback.exclude),
+            exclude_members: self.exclude_members.or(fallback.exclude_members),
+            min_members: self.min_members.or(fallback.min_members),
+            version_resolution_strategy: self
+                .version_resolution_strategy
+                .or(fallback.version_resolution_strategy),
+            // First matching override wins, so these keep both lists
+            version_resolution_overrides: self
+                .version_resolution_overrides
+                .into_iter()
+                .chain(fallback.version_resolution_overrides)
+                .collect(),
+            prerelease_policy: self.prerelease_policy.or(fallback.prerelease_policy),
+            exact_pin_policy: self.exact_pin_policy.or(fallback.exact_pin_policy),
+            respect_rust_version: self.respect_rust_version.or(fallback.respect_rust_version),
+            registry_index: self.registry_index.or(fallback.registry_index),
+            deny_breaking_changes: self
+                .deny_breaking_changes
+                .or(fallback.deny_breaking_changes),
+            partial_consolidation: self
+                .partial_consolidation
+                .or(fallback.partial_consolidation),
+            default_features_strategy: self
+                .default_features_strategy
+                .or(fallback.default_features_strategy),
+            version_format: self.version_format.or(fallback.version_format),
+            consolidate_path_dependencies: self
+                .consolidate_path_dependencies
+                .or(fallback.consolidate_path_dependencies),
+            hoist_features: self.hoist_features.or(fallback.hoist_features),
+            prune_unused: self.prune_unused.or(fallback.prune_unused),
+            demote: self.demote.or(fallback.demote),
+            process_package: self.process_package.or(fallback.process_package),
+            process_lints: self.process_lints.or(fallback.process_lints),
+        }
+    }
+}
+
+/// Settings a member sets for itself in `[package.metadata.workspace-deps]`
+/// Example:
+/// ```toml
+/// [package.metadata.workspace-deps]
+/// exclude = ["serde"]
+/// no-dev-dependencies = true
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct MemberConfig {
+    /// Leave the member out of dependency consolidation entirely
+    pub(crate) skip: bool,
+    /// Dependencies the member keeps as explicit specs
+    pub(crate) exclude: Vec<String>,
+    /// Sections the member keeps as they are
+    pub(crate) skipped_kinds: Vec<DepKind>,
+}
+
+impl MemberConfig {
+    /// Read `[package.metadata.workspace-deps]` from a member manifest
+    pub(crate) fn load(manifest_path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+        let doc = Document::parse(content.as_str())
+            .with_context(|| format!("Failed to parse TOML at {}", manifest_path.display()))?;
+        let source = Source {
+            path: manifest_path,
+            raw: &content,
+        };
+
+        let mut config = MemberConfig::default();
+        let Some(table) = find_table(&doc, &source, &["package", "metadata", "workspace-deps"])?
+        else {
+            return Ok(config);
+        };
+        for (name, item) in table.iter() {
+            let value = Setting::new(&source, table, name, item);
+
+            let kind = match name {
+                "skip" => {
+                    config.skip = value.bool()?;
+                    continue;
+                }
+                "exclude" => {
+                    config.exclude = value.strings()?;
+                    continue;
+                }
+                "no-dependencies" => DepKind::Dependencies,
+                "no-dev-dependencies" => DepKind::DevDependencies,
+                "no-build-dependencies" => DepKind::BuildDependencies,
+                _ => return Err(value.unknown_key()),
+            };
+            if value.bool()? {
+                config.skipped_kinds.push(kind);
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+/// Walk `table_path` from the document root, `None` when a table along it is missing
+fn find_table<'a>(
+    doc: &'a Document<&str>,
+    source: &Source,
+    table_path: &[&str],
+) -> Result<Option<&'a dyn TableLike>> {
+    let mut table: &dyn TableLike = doc.as_table();
+    for (depth, name) in table_path.iter().enumerate() {
+        let Some((key, item)) = table.get_key_value(name) else {
+            return Ok(None);
+        };
+        table = item.as_table_like().ok_or_else(|| {
+            source.error(
+                key.span(),
+                format!("[{}] must be a table", table_path[..=depth].join(".")),
+            )
+        })?;
+    }
+    Ok(Some(table))
+}
+
+/// A single key/value pair being read, with typed accessors that report its location
+struct Setting<'a> {
+    source: &'a Source<'a>,
+    name: &'a str,
+    item: &'a Item,
+    key_span: Option<Range<usize>>,
+    span: Option<Range<usize>>,
+}
+
+impl<'a> Setting<'a> {
+    fn new(source: &'a Source<'a>, table: &dyn TableLike, name: &'a str, item: &'a Item) -> Self {
+        let key_span = table.get_key_value(name).and_then(|(key, _)| key.span());
+        Setting {
+            source,
+            name,
+            item,
+            span: item.as_value().and_then(|v| v.span()).or(key_span.clone()),
+            key_span,
+        }
+    }
+
+    fn unknown_key(&self) -> anyhow::Error {
+        self.source.error(
+            self.key_span.clone(),
+            format!("unknown key '{}'", self.name),
+        )
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> anyhow::Error {
+        self.source.error(self.span.clone(), message)
+    }
+
+    fn bool(&self) -> Result<bool> {
+        self.item
+            .as_bool()
+            .ok_or_else(|| self.error(format!("'{}' must be a boolean", self.name)))
+    }
+
+    fn integer(&self) -> Result<i64> {
+        self.item
+            .as_integer()
+            .ok_or_else(|| self.error(format!("'{}' must be an integer", self.name)))
+    }
+
+    fn string(&self) -> Result<&str> {
+        self.item
+            .as_str()
+            .ok_or_else(|| self.error(format!("'{}' must be a string", self.name)))
+    }
+
+    fn strings(&self) -> Result<Vec<String>> {
+        let error = || self.error(format!("'{}' must be an array of strings", self.name));
+        let array = self.item.as_array().ok_or_else(error)?;
+        array
+            .iter()
+            .map(|value| value.as_str().map(str::to_string).ok_or_else(error))
+            .collect()
+    }
+
+    fn enum_value<T: ValueEnum>(&self) -> Result<T> {
+        let value = self.string()?;
+        T::from_str(value, false).map_err(|_| {
+            let expected: Vec<_> = T::value_variants()
+                .iter()
+                .filter_map(|v| v.to_possible_value())
+                .map(|v| v.get_name().to_string())
+                .collect();
+            self.error(format!(
+                "Invalid value '{}' for '{}' (expected one of: {})",
+                value,
+                self.name,
+                expected.join(", ")
+            ))
+        })
+    }
+
+    fn overrides(&self) -> Result<Vec<VersionResolutionOverride>> {
+        let table = self
+            .item
+            .as_table_like()
+            .ok_or_else(|| self.error(format!("[{}] must be a table", self.name)))?;
+        table
+            .iter()
+            .map(|(pattern, strategy)| {
+                let span = strategy
+                    .as_value()
+                    .and_then(|v| v.span())
+                    .or_else(|| table.get_key_value(pattern).and_then(|(k, _)| k.span()));
+                let strategy = strategy.as_str().ok_or_else(|| {
+                    self.source.error(
+                        span.clone(),
+                        format!("{}.{} must be a string", self.name, pattern),
+                    )
+                })?;
+                VersionResolutionOverride::new(pattern, strategy)
+                    .map_err(|e| self.source.error(span, e))
+            })
+            .collect()
+    }
+}
+
+/// Effective settings written in the format `workspace-deps.toml` accepts
+pub(crate) fn render(config: &crate::Config) -> String {
+    fn name<T: ValueEnum>(value: &T) -> String {
+        value
+            .to_possible_value()
+            .map(|v| v.get_name().to_string())
+            .unwrap_or_default()
+    }
+
+    let mut doc = toml_edit::DocumentMut::new();
+    doc["no-dependencies"] = toml_edit::value(!config.process_dependencies);
+    doc["no-dev-dependencies"] = toml_edit::value(!config.process_dev_dependencies);
+    doc["no-build-dependencies"] = toml_edit::value(!config.process_build_dependencies);
+    doc["exclude"] = toml_edit::value(config.exclude.iter().collect::<toml_edit::Array>());
+    doc["exclude-members"] = toml_edit::value(
+        config
+            .exclude_members
+            .iter()
+            .map(|pattern| pattern.as_str())
+            .collect::<toml_edit::Array>(),
+    );
+    doc["min-members"] = toml_edit::value(config.min_members as i64);
+    doc["version-resolution"] = toml_edit::value(name(&config.version_resolution_strategy));
+    doc["prerelease"] = toml_edit::value(name(&config.prerelease_policy));
+    doc["exact-pins"] = toml_edit::value(name(&config.exact_pin_policy));
+    doc["msrv"] = toml_edit::value(config.respect_rust_version);
+    if let Some(dir) = &config.registry_index {
+        doc["registry-index"] = toml_edit::value(dir.display().to_string());
+    }
+    doc["deny-breaking"] = toml_edit::value(config.deny_breaking_changes);
+    doc["partial"] = toml_edit::value(config.partial_consolidation);
+    doc["default-features-resolution"] = toml_edit::value(name(&config.default_features_strategy));
+    doc["version-format"] = toml_edit::value(name(&config.version_format));
+    doc["path-dependencies"] = toml_edit::value(config.consolidate_path_dependencies);
+    doc["hoist-features"] = toml_edit::value(config.hoist_features);
+    doc["prune-unused"] = toml_edit::value(config.prune_unused);
+    doc["demote"] = toml_edit::value(config.demote);
+    doc["package"] = toml_edit::value(config.process_package);
+    doc["lints"] = toml_edit::value(config.process_lints);
+
+    if !config.version_resolution_overrides.is_empty() {
+        let mut override_table = Table::new();
+        for entry in &config.version_resolution_overrides {
+            // First matching override wins, later duplicates never apply
+            if !override_table.contains_key(entry.pattern.as_str()) {
+                override_table[entry.pattern.as_str()] = toml_edit::value(name(&entry.strategy));
+            }
+        }
+        doc["version-resolution-override"] = Item::Table(override_table);
+    }
+
+    doc.to_string()
+}
@@ -1,11 +1,18 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
+use semver::Version;
 use serde::Serialize;
-use std::collections::HashMap;
-use std::fs;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::Path;
-use toml_edit::{DocumentMut, Item};
+use toml_edit::{DocumentMut, Item, Table};
 
-const WORKSPACE_MARKER: &str = "workspace";
+use crate::config_file::MemberConfig;
+use crate::lockfile::Lockfile;
+use crate::manifest::{WORKSPACE_MARKER, read_manifest};
+use crate::registry::{IndexEntry, RegistryIndex};
+use crate::version_resolver::{
+    DependencyKey, Resolution, VersionChange, VersionResolver, builtin_resolver, classify_change,
+};
 
 /// Result of parsing dependencies from a Cargo.toml file
 #[derive(Debug)]
@@ -17,30 +24,131 @@ pub(crate) struct ParsedDependencies {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub(crate) enum DepSection {
+pub(crate) enum DepKind {
     Dependencies,
     DevDependencies,
     BuildDependencies,
 }
 
-impl DepSection {
+impl DepKind {
     pub(crate) fn as_str(&self) -> &str {
         match self {
-            DepSection::Dependencies => "dependencies",
-            DepSection::DevDependencies => "dev-dependencies",
-            DepSection::BuildDependencies => "build-dependencies",
+            DepKind::Dependencies => "dependencies",
+            DepKind::DevDependencies => "dev-dependencies",
+            DepKind::BuildDependencies => "build-dependencies",
+        }
+    }
+}
+
+/// A dependency table, optionally scoped to a platform
+/// (e.g. `[target.'cfg(unix)'.dependencies]`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct DepSection {
+    pub(crate) kind: DepKind,
+    /// Target spec from `[target.<spec>]`, None for top-level tables
+    pub(crate) target: Option<String>,
+}
+
+impl From<DepKind> for DepSection {
+    fn from(kind: DepKind) -> Self {
+        DepSection { kind, target: None }
+    }
+}
+
+impl DepSection {
+    pub(crate) fn for_target(kind: DepKind, target: &str) -> Self {
+        DepSection {
+            kind,
+            target: Some(target.to_string()),
+        }
+    }
+
+    /// Look up this section's table in a manifest
+    pub(crate) fn table<'a>(&self, doc: &'a DocumentMut) -> Option<&'a Table> {
+        let parent = match &self.target {
+            Some(target) => match doc.get("target")?.get(target)? {
+                Item::Table(table) => table,
+                _ => return None,
+            },
+            None => doc.as_table(),
+        };
+        match parent.get(self.kind.as_str())? {
+            Item::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+
+    /// Look up this section's table in a manifest for editing
+    pub(crate) fn table_mut<'a>(&self, doc: &'a mut DocumentMut) -> Option<&'a mut Table> {
+        let parent = match &self.target {
+            Some(target) => match doc.get_mut("target")?.get_mut(target)? {
+                Item::Table(table) => table,
+                _ => return None,
+            },
+            None => doc.as_table_mut(),
+        };
+        match parent.get_mut(self.kind.as_str())? {
+            Item::Table(table) => Some(table),
+            _ => None,
         }
     }
 }
 
+/// Branch, tag or revision a git dependency is pinned to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum GitReference {
+    Branch(String),
+    Tag(String),
+    Rev(String),
+}
+
+impl GitReference {
+    /// Manifest key for this reference (`branch`, `tag` or `rev`)
+    pub(crate) fn key(&self) -> &str {
+        match self {
+            GitReference::Branch(_) => "branch",
+            GitReference::Tag(_) => "tag",
+            GitReference::Rev(_) => "rev",
+        }
+    }
+
+    pub(crate) fn value(&self) -> &str {
+        match self {
+            GitReference::Branch(v) | GitReference::Tag(v) | GitReference::Rev(v) => v,
+        }
+    }
+}
+
+impl std::fmt::Display for GitReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = \"{}\"", self.key(), self.value())
+    }
+}
+
+/// Git repository a dependency is fetched from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct GitSource {
+    /// URL as written in the manifest
+    pub(crate) url: String,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub(crate) reference: Option<GitReference>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct DependencySpec {
     pub(crate) name: String,
-    pub(crate) version: String,
+    /// Version requirement, optional for git dependencies
+    pub(crate) version: Option<String>,
     pub(crate) section: DepSection,
     pub(crate) package: Option<String>,
     pub(crate) registry: Option<String>,
     pub(crate) default_features: bool,
+    /// Local path, rebased onto the workspace root once resolved to a member
+    pub(crate) path: Option<String>,
+    pub(crate) git: Option<GitSource>,
+    /// Features requested by this declaration
+    pub(crate) features: Vec<String>,
 }
 
 /// All parsed dependency data from workspace and members
@@ -49,6 +157,53 @@ pub(crate) struct WorkspaceData {
     pub(crate) member_deps: HashMap<String, Vec<DependencySpec>>,
     // Deps already using { workspace = true }
     pub(crate) workspace_refs: Vec<(String, DepSection)>,
+    /// Members inheriting each workspace dependency
+    pub(crate) workspace_ref_members: HashMap<String, BTreeSet<String>>,
+    /// Dependencies members left out through `[package.metadata.workspace-deps]`
+    pub(crate) skipped: Vec<SkippedDependencies>,
+    /// Workspace dependencies inherited where a member opted out, never pruned or demoted
+    pub(crate) opted_out_refs: HashSet<String>,
+}
+
+impl WorkspaceData {
+    /// Members whose metadata sets `skip = true`
+    pub(crate) fn skipped_members(&self) -> HashSet<&str> {
+        self.skipped
+            .iter()
+            .filter(|skipped| skipped.reason == SkipReason::Member)
+            .map(|skipped| skipped.member.as_str())
+            .collect()
+    }
+}
+
+/// Why a member's own metadata left dependencies out of consolidation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SkipReason {
+    /// `skip = true`
+    Member,
+    /// `no-<kind> = true`
+    Section(DepKind),
+    /// Named in `exclude`
+    Excluded,
+}
+
+impl SkipReason {
+    /// The metadata key responsible
+    pub(crate) fn key(&self) -> String {
+        match self {
+            SkipReason::Member => "skip".to_string(),
+            SkipReason::Section(kind) => format!("no-{}", kind.as_str()),
+            SkipReason::Excluded => "exclude".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SkippedDependencies {
+    pub(crate) member: String,
+    pub(crate) reason: SkipReason,
+    /// Explicit dependencies left out, sorted and deduplicated
+    pub(crate) dependencies: Vec<String>,
 }
 
 /// Key for grouping dependencies that should share a workspace entry
@@ -57,6 +212,78 @@ struct WorkspaceDepKey {
     name: String,
     package: Option<String>,
     registry: Option<String>,
+    /// Normalized git URL, see `normalize_git_url`
+    git: Option<String>,
+    /// Member path relative to the workspace root
+    path: Option<String>,
+}
+
+impl WorkspaceDepKey {
+    /// What a `VersionResolver` gets to see of the key
+    fn public(&self) -> DependencyKey {
+        DependencyKey {
+            name: self.name.clone(),
+            package: self.package.clone(),
+            registry: self.registry.clone(),
+        }
+    }
+
+    fn new(dep: &DependencySpec) -> Self {
+        WorkspaceDepKey {
+            name: dep.name.clone(),
+            package: dep.package.clone(),
+            registry: dep.registry.clone(),
+            git: dep.git.as_ref().map(|git| normalize_git_url(&git.url)),
+            path: dep.path.clone(),
+        }
+    }
+
+    /// Where the dependency comes from, e.g. "crates.io" or "github.com/org/repo"
+    fn source(&self) -> String {
+        let mut source = match (&self.git, &self.path, &self.registry) {
+            (Some(git), _, _) => git.clone(),
+            (None, Some(path), _) => format!("path {}", path),
+            (None, None, Some(registry)) => format!("registry {}", registry),
+            (None, None, None) => "crates.io".to_string(),
+        };
+        if let Some(package) = &self.package {
+            source.push_str(&format!(" (package {})", package));
+        }
+        source
+    }
+}
+
+/// Normalize a git URL so that equivalent forms of the same repository compare equal
+/// Examples: "https://github.com/org/repo.git", "git@github.com:org/repo"
+/// and "ssh://git@github.com/org/repo/" all become "github.com/org/repo"
+pub(crate) fn normalize_git_url(url: &str) -> String {
+    let url = url.trim();
+
+    // Strip the scheme, remembering whether there was one (scp-like syntax has none)
+    let (has_scheme, rest) = match url.split_once("://") {
+        Some((_, rest)) => (true, rest),
+        None => (false, url),
+    };
+
+    // Strip user info (e.g. "git@")
+    let rest = match rest.split_once('@') {
+        Some((user, host_and_path)) if !user.contains('/') => host_and_path,
+        _ => rest,
+    };
+
+    let (host, path) = if has_scheme {
+        rest.split_once('/').unwrap_or((rest, ""))
+    } else {
+        // scp-like: "github.com:org/repo"
+        rest.split_once(':')
+            .or_else(|| rest.split_once('/'))
+            .unwrap_or((rest, ""))
+    };
+
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path).trim_matches('/');
+
+    format!("{}/{}", host.to_lowercase(), path)
 }
 
 #[derive(Debug)]
@@ -70,12 +297,26 @@ pub(crate) struct DependencyAnalysis {
 
     /// Workspace dependencies that are not used by any member
     pub(crate) unused_workspace_deps: Vec<String>,
+
+    /// Workspace dependencies used by too few members, only filled in demote mode
+    pub(crate) demoted_deps: Vec<DemotedDependency>,
+
+    /// Dependencies members left out through their own metadata
+    pub(crate) skipped: Vec<SkippedDependencies>,
+}
+
+/// A workspace dependency moved back into the members inheriting it
+#[derive(Debug, Clone)]
+pub(crate) struct DemotedDependency {
+    pub(crate) name: String,
+    /// Members that get an explicit spec instead of `workspace = true`
+    pub(crate) members: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct CommonDependency {
     pub(crate) name: String,
-    pub(crate) version: String,
+    pub(crate) version: Option<String>,
     /// Members and their sections that use this dependency
     pub(crate) members: Vec<(String, DepSection)>,
     /// Renamed package (e.g., serde_crate = { package = "serde", ... })
@@ -84,17 +325,54 @@ pub(crate) struct CommonDependency {
     pub(crate) registry: Option<String>,
     /// Whether to disable default features
     pub(crate) default_features: bool,
+    /// Member path relative to the workspace root for path dependencies
+    pub(crate) path: Option<String>,
+    /// Git repository and reference for git dependencies
+    pub(crate) git: Option<GitSource>,
+    /// Features requested by every member, hoisted into the workspace entry
+    pub(crate) features: Vec<String>,
     /// Original version map if this was resolved from a conflict
     pub(crate) resolved_from: Option<HashMap<String, Vec<String>>>,
+    /// Strategy used to resolve the version conflict if it differs from the global one
+    pub(crate) resolution_override: Option<crate::VersionResolutionStrategy>,
+    /// Number of members whose requirement changes to the resolved version
+    pub(crate) members_moved: usize,
+    /// Exact pins (`=x.y.z`) involved in a version conflict, mapped to the members using them
+    pub(crate) pins: HashMap<String, Vec<String>>,
+    /// Versions kept by members outside the consolidated group, mapped to the members using them
+    pub(crate) diverging: HashMap<String, Vec<String>>,
+    /// How each member's requirement changes if this was resolved from a conflict
+    pub(crate) member_changes: BTreeMap<String, VersionChange>,
+    /// Original default-features map ("true"/"false" -> members) if this was resolved from a conflict
+    pub(crate) default_features_resolved_from: Option<HashMap<String, Vec<String>>>,
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct VersionSpec {
-    pub(crate) version: String,
+    pub(crate) version: Option<String>,
     pub(crate) default_features: bool,
+    pub(crate) git_reference: Option<GitReference>,
     pub(crate) members: Vec<String>,
 }
 
+/// Fields that distinguish declarations of the same dependency
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+struct SpecKey {
+    version: Option<String>,
+    default_features: bool,
+    git_reference: Option<GitReference>,
+}
+
+impl SpecKey {
+    fn new(dep: &DependencySpec) -> Self {
+        SpecKey {
+            version: dep.version.clone(),
+            default_features: dep.default_features,
+            git_reference: dep.git.as_ref().and_then(|git| git.reference.clone()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 struct VersionUsage {
     /// Members and their sections that use this version
@@ -103,16 +381,69 @@ struct VersionUsage {
     in_workspace: bool,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 struct DependencyTracker {
-    /// Maps (version, default_features) -> usage info
-    version_specs: HashMap<(String, bool), VersionUsage>,
+    /// Maps (version, default_features, git reference) -> usage info
+    version_specs: HashMap<SpecKey, VersionUsage>,
+    /// Git URLs as written by members and the workspace, with how often each is used
+    git_urls: BTreeMap<String, usize>,
+    /// Features requested by each member declaration
+    member_features: Vec<BTreeSet<String>>,
+    /// Features already enabled by the [workspace.dependencies] entry
+    workspace_features: BTreeSet<String>,
+    /// Whether some members already inherit the workspace entry with `workspace = true`
+    has_workspace_refs: bool,
 }
 
 impl DependencyTracker {
-    /// Get unique versions
+    fn track(&mut self, dep: &DependencySpec) -> &mut VersionUsage {
+        if let Some(git) = &dep.git {
+            *self.git_urls.entry(git.url.clone()).or_default() += 1;
+        }
+        self.version_specs.entry(SpecKey::new(dep)).or_default()
+    }
+
+    /// Get unique versions (git dependencies may not declare one)
     fn unique_versions(&self) -> std::collections::HashSet<String> {
-        self.version_specs.keys().map(|(v, _)| v.clone()).collect()
+        self.version_specs
+            .keys()
+            .filter_map(|spec| spec.version.clone())
+            .collect()
+    }
+
+    /// Get unique git references
+    fn unique_git_references(&self) -> std::collections::HashSet<Option<GitReference>> {
+        self.version_specs
+            .keys()
+            .map(|spec| spec.git_reference.clone())
+            .collect()
+    }
+
+    /// Git source to write to the workspace entry
+    /// Prefers https URLs, which work without ssh keys (e.g. in CI), then the most used one
+    fn git_source(&self) -> Option<GitSource> {
+        let (url, _) = self
+            .git_urls
+            .iter()
+            .max_by_key(|(url, count)| (url.starts_with("https://"), **count, Reverse(*url)))?;
+        let reference = self.unique_git_references().into_iter().next().flatten();
+        Some(GitSource {
+            url: url.clone(),
+            reference,
+        })
+    }
+
+    /// Features shared by every member declaration, plus those already in the workspace entry
+    fn common_features(&self) -> Vec<String> {
+        let mut declarations = self.member_features.iter();
+        let shared = match declarations.next() {
+            Some(first) => declarations.fold(first.clone(), |shared, features| {
+                shared.intersection(features).cloned().collect()
+            }),
+            None => BTreeSet::new(),
+        };
+
+        shared.union(&self.workspace_features).cloned().collect()
     }
 
     /// Count of unique versions
@@ -134,19 +465,73 @@ impl DependencyTracker {
             .collect()
     }
 
+    /// Take members pinning an exact version out of the tracker, returning pin -> members
+    fn remove_exact_pins(&mut self) -> HashMap<String, Vec<String>> {
+        self.remove_versions(crate::version_resolver::is_exact_pin)
+    }
+
+    /// Take members outside the largest SemVer-compatible group out of the tracker,
+    /// returning version -> members
+    fn remove_diverging_members(&mut self) -> HashMap<String, Vec<String>> {
+        let group = crate::version_resolver::largest_compatible_group(&self.build_version_map());
+        self.remove_versions(|version| !group.contains(version))
+    }
+
+    /// Take members declaring a matching version out of the tracker, returning version -> members
+    /// The workspace entry stays, it is rewritten either way
+    fn remove_versions(&mut self, remove: impl Fn(&str) -> bool) -> HashMap<String, Vec<String>> {
+        let mut removed: HashMap<String, Vec<String>> = HashMap::new();
+        self.version_specs.retain(|spec, usage| {
+            let Some(version) = spec.version.as_deref().filter(|v| remove(v)) else {
+                return true;
+            };
+            if !usage.members.is_empty() {
+                removed
+                    .entry(version.to_string())
+                    .or_default()
+                    .extend(usage.members.drain(..).map(|(name, _)| name));
+            }
+            usage.in_workspace
+        });
+        removed
+    }
+
     /// Get all default_features values for a specific version
-    fn get_default_features_for_version(&self, version: &str) -> Vec<bool> {
+    /// (including declarations without a version)
+    fn get_default_features_for_version(&self, version: Option<&str>) -> Vec<bool> {
         self.version_specs
-            .iter()
-            .filter_map(|((v, df), _)| (v == version).then_some(*df))
+            .keys()
+            .filter(|spec| spec.version.is_none() || spec.version.as_deref() == version)
+            .map(|spec| spec.default_features)
             .collect()
     }
 
+    /// Build default-features map ("true"/"false" -> members) for a specific version
+    fn build_default_features_map(&self, version: Option<&str>) -> HashMap<String, Vec<String>> {
+        let mut result: HashMap<String, Vec<String>> = HashMap::new();
+
+        for (spec, usage) in &self.version_specs {
+            if spec.version.is_some() && spec.version.as_deref() != version {
+                continue;
+            }
+            // Only members are listed, the workspace entry is rewritten either way
+            result
+                .entry(spec.default_features.to_string())
+                .or_default()
+                .extend(usage.members.iter().map(|(name, _)| name.clone()));
+        }
+
+        result
+    }
+
     /// Build version map for version resolver (groups by version, aggregates members)
     fn build_version_map(&self) -> HashMap<String, Vec<String>> {
         let mut result: HashMap<String, VersionUsage> = HashMap::new();
 
-        for ((version, _df), usage) in &self.version_specs {
+        for (spec, usage) in &self.version_specs {
+            let Some(version) = &spec.version else {
+                continue;
+            };
             let entry = result.entry(version.clone()).or_default();
             entry.members.extend(usage.members.iter().cloned());
             entry.in_workspace |= usage.in_workspace;
@@ -174,6 +559,10 @@ impl VersionUsage {
 pub(crate) enum ConflictType {
     VersionResolution,
     DefaultFeatures,
+    GitReference,
+    Prerelease,
+    ExactPin,
+    RustVersion,
 }
 
 #[derive(Debug, Clone)]
@@ -181,6 +570,8 @@ pub(crate) struct ConflictingDependency {
     pub(crate) name: String,
     pub(crate) version_specs: Vec<VersionSpec>,
     pub(crate) conflict_types: Vec<ConflictType>,
+    /// Why the requirements could not be combined, e.g. which ones do not overlap
+    pub(crate) diagnostics: Vec<String>,
 }
 
 /// Get common default_features value from a list, returning the unique value if all agree
@@ -193,6 +584,27 @@ fn get_common_default_features(values: &[bool]) -> bool {
     }
 }
 
+macro_rules! extract_git_source {
+    ($table:expr) => {{
+        $table.get("git").and_then(|v| v.as_str()).map(|url| {
+            let field = |key: &str| {
+                $table
+                    .get(key)
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+            };
+            let reference = field("rev")
+                .map(GitReference::Rev)
+                .or_else(|| field("tag").map(GitReference::Tag))
+                .or_else(|| field("branch").map(GitReference::Branch));
+            GitSource {
+                url: url.to_string(),
+                reference,
+            }
+        })
+    }};
+}
+
 macro_rules! extract_fields {
     ($table:expr) => {{
         let version = $table
@@ -208,7 +620,30 @@ macro_rules! extract_fields {
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
         let default_features = $table.get("default-features").and_then(|v| v.as_bool());
-        (version, package, registry, default_features)
+        let path = $table
+            .get("path")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        let git = extract_git_source!($table);
+        let features: Vec<String> = $table
+            .get("features")
+            .and_then(|v| v.as_array())
+            .map(|array| {
+                array
+                    .iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        (
+            version,
+            package,
+            registry,
+            default_features,
+            path,
+            git,
+            features,
+        )
     }};
 }
 
@@ -218,12 +653,12 @@ fn extract_dependency_spec(name: &str, item: &Item, section: DepSection) -> Opti
     match item {
         Item::Value(val) if val.is_inline_table() => {
             let table = val.as_inline_table()?;
-            // Skip path or git dependencies
-            if table.contains_key("path") || table.contains_key("git") {
+            let (version, package, registry, default_features, path, git, features) =
+                extract_fields!(table);
+            // Require version for registry deps
+            if version.is_none() && path.is_none() && git.is_none() {
                 return None;
             }
-            let (version, package, registry, default_features) = extract_fields!(table);
-            let version = version?; // Require version for member deps
             Some(DependencySpec {
                 name: name.to_string(),
                 version,
@@ -231,23 +666,29 @@ fn extract_dependency_spec(name: &str, item: &Item, section: DepSection) -> Opti
                 package,
                 registry,
                 default_features: default_features.unwrap_or(true),
+                path,
+                git,
+                features,
             })
         }
         Item::Value(val) => val.as_str().map(|s| DependencySpec {
             name: name.to_string(),
-            version: s.to_string(),
+            version: Some(s.to_string()),
             section,
             package: None,
             registry: None,
             default_features: true,
+            path: None,
+            git: None,
+            features: Vec::new(),
         }),
         Item::Table(table) => {
-            // Skip path or git dependencies
-            if table.contains_key("path") || table.contains_key("git") {
+            let (version, package, registry, default_features, path, git, features) =
+                extract_fields!(table);
+            // Require version for registry deps
+            if version.is_none() && path.is_none() && git.is_none() {
                 return None;
             }
-            let (version, package, registry, default_features) = extract_fields!(table);
-            let version = version?; // Require version for member deps
             Some(DependencySpec {
                 name: name.to_string(),
                 version,
@@ -255,6 +696,9 @@ fn extract_dependency_spec(name: &str, item: &Item, section: DepSection) -> Opti
                 package,
                 registry,
                 default_features: default_features.unwrap_or(true),
+                path,
+                git,
+                features,
             })
         }
         _ => None,
@@ -262,7 +706,7 @@ fn extract_dependency_spec(name: &str, item: &Item, section: DepSection) -> Opti
 }
 
 /// Check if a dependency item uses workspace inheritance
-fn uses_workspace_inheritance(item: &Item) -> bool {
+pub(crate) fn uses_workspace_inheritance(item: &Item) -> bool {
     match item {
         Item::Table(t) => t.contains_key("workspace"),
         Item::Value(val) if val.is_inline_table() => val
@@ -282,34 +726,46 @@ fn process_dependency_section(
 ) {
     for (name, item) in table.iter() {
         if uses_workspace_inheritance(item) {
-            workspace_refs.push((name.to_string(), section));
+            workspace_refs.push((name.to_string(), section.clone()));
             continue;
         }
 
-        if let Some(dep_spec) = extract_dependency_spec(name, item, section) {
+        if let Some(dep_spec) = extract_dependency_spec(name, item, section.clone()) {
             deps.push(dep_spec);
         }
     }
 }
 
+/// Collect the top-level and platform-specific tables for the given kinds
+pub(crate) fn collect_sections(doc: &DocumentMut, kinds: &[DepKind]) -> Vec<DepSection> {
+    let targets: Vec<&str> = match doc.get("target") {
+        Some(Item::Table(targets)) => targets.iter().map(|(spec, _)| spec).collect(),
+        _ => Vec::new(),
+    };
+
+    let mut sections = Vec::new();
+    for kind in kinds {
+        sections.push(DepSection::from(*kind));
+        for target in &targets {
+            sections.push(DepSection::for_target(*kind, target));
+        }
+    }
+    sections
+}
+
 /// Parse dependencies from a Cargo.toml file
 pub(crate) fn parse_dependencies(
     manifest_path: &Path,
-    sections: &[DepSection],
+    kinds: &[DepKind],
 ) -> Result<ParsedDependencies> {
-    let content = fs::read_to_string(manifest_path)
-        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
-
-    let doc = content
-        .parse::<DocumentMut>()
-        .with_context(|| format!("Failed to parse TOML at {}", manifest_path.display()))?;
+    let doc = read_manifest(manifest_path)?;
 
     let mut deps = Vec::new();
     let mut workspace_refs = Vec::new();
 
-    for section in sections {
-        if let Some(Item::Table(table)) = doc.get(section.as_str()) {
-            process_dependency_section(table, *section, &mut deps, &mut workspace_refs);
+    for section in collect_sections(&doc, kinds) {
+        if let Some(table) = section.table(&doc) {
+            process_dependency_section(table, section.clone(), &mut deps, &mut workspace_refs);
         }
     }
 
@@ -321,14 +777,10 @@ pub(crate) fn parse_dependencies(
 
 /// Parse workspace dependencies from [workspace.dependencies]
 pub(crate) fn parse_workspace_dependencies(
-    workspace_manifest: &Path,
+    workspace_info: &crate::workspace::WorkspaceInfo,
 ) -> Result<HashMap<String, DependencySpec>> {
-    let content = fs::read_to_string(workspace_manifest)
-        .with_context(|| format!("Failed to read {}", workspace_manifest.display()))?;
-
-    let doc = content
-        .parse::<DocumentMut>()
-        .with_context(|| format!("Failed to parse TOML at {}", workspace_manifest.display()))?;
+    let workspace_manifest = &workspace_info.root_manifest;
+    let doc = read_manifest(workspace_manifest)?;
 
     let mut workspace_deps = HashMap::new();
 
@@ -336,25 +788,43 @@ pub(crate) fn parse_workspace_dependencies(
         && let Some(Item::Table(deps_table)) = workspace.get("dependencies")
     {
         for (name, item) in deps_table.iter() {
-            let (version, package, registry, default_features) = match item {
+            let (version, package, registry, default_features, path, git, features) = match item {
                 Item::Value(val) if val.is_inline_table() => val
                     .as_inline_table()
-                    .map_or((None, None, None, None), |table| extract_fields!(table)),
-                Item::Value(val) => (val.as_str().map(|s| s.to_string()), None, None, None),
+                    .map_or(Default::default(), |table| extract_fields!(table)),
+                Item::Value(val) => (
+                    val.as_str().map(|s| s.to_string()),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    Vec::new(),
+                ),
                 Item::Table(table) => extract_fields!(table),
-                _ => (None, None, None, None),
+                _ => Default::default(),
             };
 
-            if let Some(version) = version {
+            // Paths in the workspace table are already relative to the root
+            let path = path.map(|p| {
+                workspace_info
+                    .member_path_from(&workspace_info.root_manifest, &p)
+                    .unwrap_or(p)
+            });
+
+            if version.is_some() || path.is_some() || git.is_some() {
                 workspace_deps.insert(
                     name.to_string(),
                     DependencySpec {
                         name: name.to_string(),
                         version,
-                        section: DepSection::Dependencies, // placeholder, actual section comes from member
+                        section: DepKind::Dependencies.into(), // placeholder, actual section comes from member
                         package,
                         registry,
                         default_features: default_features.unwrap_or(true),
+                        path,
+                        git,
+                        features,
                     },
                 );
             }
@@ -364,31 +834,99 @@ pub(crate) fn parse_workspace_dependencies(
     Ok(workspace_deps)
 }
 
-const ALL_SECTIONS: &[DepSection] = &[
-    DepSection::Dependencies,
-    DepSection::DevDependencies,
-    DepSection::BuildDependencies,
+pub(crate) const ALL_KINDS: &[DepKind] = &[
+    DepKind::Dependencies,
+    DepKind::DevDependencies,
+    DepKind::BuildDependencies,
 ];
 
 /// Parse all workspace data (workspace deps + member deps)
 pub(crate) fn parse_workspace_data(
     workspace_info: &crate::workspace::WorkspaceInfo,
-    sections: &[DepSection],
+    kinds: &[DepKind],
+    consolidate_path_dependencies: bool,
 ) -> Result<WorkspaceData> {
-    let workspace_deps = parse_workspace_dependencies(&workspace_info.root_manifest)?;
+    let workspace_deps = parse_workspace_dependencies(workspace_info)?;
 
     let mut member_deps = HashMap::new();
     let mut all_workspace_refs = Vec::new();
+    let mut workspace_ref_members: HashMap<String, BTreeSet<String>> = HashMap::new();
+    let mut skipped = Vec::new();
+    let mut opted_out_refs = HashSet::new();
 
     for member in &workspace_info.members {
+        let member_config = MemberConfig::load(&member.manifest_path)?;
+
         // Only collect explicit deps from enabled sections (for consolidation)
-        let parsed = parse_dependencies(&member.manifest_path, sections)?;
-        if !parsed.explicit_deps.is_empty() {
-            member_deps.insert(member.name.clone(), parsed.explicit_deps);
+        let parsed = parse_dependencies(&member.manifest_path, kinds)?;
+        let mut explicit_deps: Vec<_> = parsed
+            .explicit_deps
+            .into_iter()
+            .filter_map(|mut dep| {
+                let Some(path) = &dep.path else {
+                    return Some(dep);
+                };
+                // Path dependencies are only consolidated when they point at another member
+                if !consolidate_path_dependencies {
+                    return None;
+                }
+                dep.path = Some(workspace_info.member_path_from(&member.manifest_path, path)?);
+                Some(dep)
+            })
+            .collect();
+
+        // The member's own metadata, most general reason first
+        let mut reasons = Vec::new();
+        if member_config.skip {
+            reasons.push(SkipReason::Member);
+        }
+        reasons.extend(
+            member_config
+                .skipped_kinds
+                .iter()
+                .copied()
+                .map(SkipReason::Section),
+        );
+        if !member_config.exclude.is_empty() {
+            reasons.push(SkipReason::Excluded);
+        }
+        for reason in reasons {
+            let skips = |dep: &DependencySpec| match &reason {
+                SkipReason::Member => true,
+                SkipReason::Section(kind) => dep.section.kind == *kind,
+                SkipReason::Excluded => member_config.exclude.contains(&dep.name),
+            };
+            let (left_out, kept): (Vec<_>, Vec<_>) = explicit_deps.into_iter().partition(skips);
+            explicit_deps = kept;
+
+            let dependencies: BTreeSet<_> = left_out.into_iter().map(|dep| dep.name).collect();
+            // A skipped member is reported even when it has nothing to consolidate
+            if !dependencies.is_empty() || reason == SkipReason::Member {
+                skipped.push(SkippedDependencies {
+                    member: member.name.clone(),
+                    reason,
+                    dependencies: dependencies.into_iter().collect(),
+                });
+            }
+        }
+        if !explicit_deps.is_empty() {
+            member_deps.insert(member.name.clone(), explicit_deps);
         }
 
         // Always scan all sections for workspace refs (for unused dep detection)
-        let all_parsed = parse_dependencies(&member.manifest_path, ALL_SECTIONS)?;
+        let all_parsed = parse_dependencies(&member.manifest_path, ALL_KINDS)?;
+        for (name, section) in &all_parsed.workspace_refs {
+            workspace_ref_members
+                .entry(name.clone())
+                .or_default()
+                .insert(member.name.clone());
+            if member_config.skip
+                || member_config.skipped_kinds.contains(&section.kind)
+                || member_config.exclude.contains(name)
+            {
+                opted_out_refs.insert(name.clone());
+            }
+        }
         all_workspace_refs.extend(all_parsed.workspace_refs);
     }
 
@@ -396,6 +934,9 @@ pub(crate) fn parse_workspace_data(
         workspace_deps,
         member_deps,
         workspace_refs: all_workspace_refs,
+        workspace_ref_members,
+        skipped,
+        opted_out_refs,
     })
 }
 
@@ -410,20 +951,12 @@ fn track_workspace_dependencies(
     trackers: &mut HashMap<WorkspaceDepKey, DependencyTracker>,
     workspace_deps: &HashMap<String, DependencySpec>,
 ) {
-    for (name, ws_dep) in workspace_deps {
-        let key = WorkspaceDepKey {
-            name: name.clone(),
-            package: ws_dep.package.clone(),
-            registry: ws_dep.registry.clone(),
-        };
-
-        trackers
-            .entry(key)
-            .or_default()
-            .version_specs
-            .entry((ws_dep.version.clone(), ws_dep.default_features))
-            .or_default()
-            .in_workspace = true;
+    for ws_dep in workspace_deps.values() {
+        let tracker = trackers.entry(WorkspaceDepKey::new(ws_dep)).or_default();
+        tracker.track(ws_dep).in_workspace = true;
+        tracker
+            .workspace_features
+            .extend(ws_dep.features.iter().cloned());
     }
 }
 
@@ -434,24 +967,95 @@ fn track_member_dependencies(
 ) {
     for (member_name, deps) in member_deps {
         for dep in deps {
-            let key = WorkspaceDepKey {
-                name: dep.name.clone(),
-                package: dep.package.clone(),
-                registry: dep.registry.clone(),
-            };
-
-            trackers
-                .entry(key)
-                .or_default()
-                .version_specs
-                .entry((dep.version.clone(), dep.default_features))
-                .or_default()
+            let tracker = trackers.entry(WorkspaceDepKey::new(dep)).or_default();
+            tracker
+                .track(dep)
                 .members
-                .push((member_name.clone(), dep.section));
+                .push((member_name.clone(), dep.section.clone()));
+            tracker
+                .member_features
+                .push(dep.features.iter().cloned().collect());
+        }
+    }
+}
+
+/// How conflicting declarations of a dependency are resolved
+pub(crate) struct ResolutionOptions<'a> {
+    pub(crate) version_resolution_strategy: &'a crate::VersionResolutionStrategy,
+    /// Custom resolver used instead of `version_resolution_strategy`
+    pub(crate) version_resolver: Option<&'a dyn VersionResolver>,
+    /// Per-dependency strategies, the first matching pattern wins
+    pub(crate) version_resolution_overrides: &'a [crate::VersionResolutionOverride],
+    pub(crate) prerelease_policy: &'a crate::PrereleasePolicy,
+    pub(crate) exact_pin_policy: &'a crate::ExactPinPolicy,
+    pub(crate) default_features_strategy: &'a crate::DefaultFeaturesStrategy,
+    /// Parsed Cargo.lock, only loaded for the Lockfile strategy
+    pub(crate) lockfile: Option<&'a Lockfile>,
+    /// Local registry index, only loaded for --msrv and the LatestCompatible strategy
+    pub(crate) registry_index: Option<&'a RegistryIndex>,
+    /// Skip versions needing a newer Rust than the members' rust-version
+    pub(crate) respect_rust_version: bool,
+    /// Consolidate the largest compatible group when the versions conflict
+    pub(crate) partial_consolidation: bool,
+    /// rust-version by member name, the workspace's own under the workspace marker
+    pub(crate) rust_versions: &'a HashMap<String, Version>,
+}
+
+impl ResolutionOptions<'_> {
+    /// Strategy a per-dependency override sets for a dependency, matching overrides
+    /// against both the dependency name and the renamed package
+    fn override_for(&self, key: &WorkspaceDepKey) -> Option<&crate::VersionResolutionStrategy> {
+        self.version_resolution_overrides
+            .iter()
+            .find(|o| {
+                o.pattern.matches(&key.name)
+                    || key.package.as_deref().is_some_and(|p| o.pattern.matches(p))
+            })
+            .map(|o| &o.strategy)
+    }
+
+    /// Published versions of a dependency, empty unless the index is loaded
+    /// and the dependency comes from crates.io
+    fn published_versions(&self, key: &WorkspaceDepKey) -> Vec<IndexEntry> {
+        match self.registry_index {
+            Some(index) if key.registry.is_none() => {
+                index.versions(key.package.as_deref().unwrap_or(&key.name))
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Lowest rust-version among the users of a dependency, `None` unless
+    /// rust-version is respected and one of them declares it
+    fn msrv_for(&self, version_map: &HashMap<String, Vec<String>>) -> Option<&Version> {
+        if !self.respect_rust_version {
+            return None;
         }
+        version_map
+            .values()
+            .flatten()
+            .filter_map(|member| self.rust_versions.get(member))
+            .min()
     }
 }
 
+/// rust-version declared by each member, and by the workspace under the workspace marker
+pub(crate) fn collect_rust_versions(
+    workspace_info: &crate::workspace::WorkspaceInfo,
+) -> HashMap<String, Version> {
+    workspace_info
+        .members
+        .iter()
+        .filter_map(|member| Some((member.name.clone(), member.rust_version.clone()?)))
+        .chain(
+            workspace_info
+                .rust_version
+                .clone()
+                .map(|version| (WORKSPACE_MARKER.to_string(), version)),
+        )
+        .collect()
+}
+
 /// Process a dependency and resolve to a common version
 fn process_dependency(
     key: &WorkspaceDepKey,
@@ -459,24 +1063,160 @@ fn process_dependency(
     has_workspace: bool,
     all_members: &[(String, DepSection)],
     min_members: usize,
-    resolution_strategy: &crate::VersionResolutionStrategy,
+    options: &ResolutionOptions,
 ) -> Result<Option<CommonDependency>, ConflictingDependency> {
     let mut conflict_types = Vec::new();
+    let mut diagnostics = Vec::new();
     let version_count = tracker.version_count();
 
-    // Try to resolve version
-    let version_resolution = if version_count == 1 {
-        let version = tracker.unique_versions().into_iter().next().unwrap();
+    let versions = tracker.unique_versions();
+    let prerelease_only = *options.prerelease_policy == crate::PrereleasePolicy::Deny
+        && !versions.is_empty()
+        && versions
+            .iter()
+            .all(|v| crate::version_resolver::is_prerelease(v));
+
+    // Try to resolve version (git dependencies may not declare any)
+    let version_resolution = if prerelease_only {
+        conflict_types.push(ConflictType::Prerelease);
+        None
+    } else if version_count <= 1 {
+        let version = tracker.unique_versions().into_iter().next();
         Some((version, None))
     } else {
         let member_lists_map = tracker.build_version_map();
-        match crate::version_resolver::resolve_version_conflict(
+        let locked = options.lockfile.map_or(&[][..], |lockfile| {
+            lockfile.versions(key.package.as_deref().unwrap_or(&key.name))
+        });
+        let stable = crate::version_resolver::apply_prerelease_policy(
             &member_lists_map,
-            resolution_strategy,
-        ) {
-            Ok((version, _)) => Some((version, Some(member_lists_map))),
-            Err(_) => {
-                conflict_types.push(ConflictType::VersionResolution);
+            options.prerelease_policy,
+        );
+        let mut candidates = stable.clone();
+        let mut published = options.published_versions(key);
+        // rust-version needed by each requirement the MSRV rules out
+        let mut too_new: HashMap<String, String> = HashMap::new();
+        if let Some(msrv) = options.msrv_for(&member_lists_map) {
+            let compatible = crate::version_resolver::apply_msrv(&candidates, &published, msrv);
+            for raw in candidates
+                .keys()
+                .filter(|raw| !compatible.contains_key(*raw))
+            {
+                if let Some(rust) = crate::version_resolver::required_rust_version(raw, &published)
+                {
+                    too_new.insert(
+                        raw.clone(),
+                        crate::version_resolver::format_version(rust, 2),
+                    );
+                }
+            }
+            if compatible.is_empty() {
+                let mut needed: Vec<String> = candidates
+                    .keys()
+                    .filter_map(|raw| {
+                        let rust = crate::version_resolver::required_rust_version(raw, &published)?;
+                        Some(format!(
+                            "'{}' needs {}",
+                            raw,
+                            crate::version_resolver::format_version(rust, 2)
+                        ))
+                    })
+                    .collect();
+                needed.sort();
+                diagnostics.push(format!(
+                    "No version builds with rust-version {}: {}",
+                    crate::version_resolver::format_version(msrv, 2),
+                    needed.join(", ")
+                ));
+            }
+            candidates = compatible;
+            // LatestCompatible must not pick a version the MSRV rules out either
+            published.retain(|entry| entry.rust_version.as_ref().is_none_or(|rust| rust <= msrv));
+        }
+        // Excluded pins were already taken out of the tracker
+        let pinned: Vec<String> = member_lists_map
+            .keys()
+            .filter(|v| crate::version_resolver::is_exact_pin(v))
+            .cloned()
+            .collect();
+        match (options.exact_pin_policy, pinned.as_slice()) {
+            _ if candidates.is_empty() => {
+                conflict_types.push(ConflictType::RustVersion);
+                None
+            }
+            (_, []) | (crate::ExactPinPolicy::ExcludeMember, _) => {
+                let builtin;
+                let resolver: &dyn VersionResolver =
+                    match (options.override_for(key), options.version_resolver) {
+                        (None, Some(custom)) => custom,
+                        (strategy, _) => {
+                            builtin = builtin_resolver(
+                                *strategy.unwrap_or(options.version_resolution_strategy),
+                                locked,
+                                &published,
+                            );
+                            builtin.as_ref()
+                        }
+                    };
+                match resolver.resolve(&key.public(), &candidates) {
+                    // Custom resolvers may return anything, keep it out of the manifests
+                    Resolution::Resolved(version)
+                        if semver::VersionReq::parse(&version).is_err() =>
+                    {
+                        conflict_types.push(ConflictType::VersionResolution);
+                        diagnostics.push(format!(
+                            "{} resolved to '{}', which is not a valid version requirement",
+                            resolver.name(),
+                            version
+                        ));
+                        None
+                    }
+                    Resolution::Resolved(version) => {
+                        // Requirements kept out of the choice still move onto it,
+                        // so they must not need a breaking change to get there
+                        let prereleases = crate::version_resolver::breaking_exclusions(
+                            &member_lists_map,
+                            &stable,
+                            &version,
+                        );
+                        let needs_newer_rust = crate::version_resolver::breaking_exclusions(
+                            &stable,
+                            &candidates,
+                            &version,
+                        );
+                        if !prereleases.is_empty() {
+                            conflict_types.push(ConflictType::VersionResolution);
+                        }
+                        if !needs_newer_rust.is_empty() {
+                            conflict_types.push(ConflictType::RustVersion);
+                        }
+                        diagnostics.extend(prereleases.iter().map(|raw| {
+                            format!(
+                                "'{}' is a prerelease, moving it to '{}' would be breaking",
+                                raw, version
+                            )
+                        }));
+                        diagnostics.extend(needs_newer_rust.iter().map(|raw| {
+                            format!(
+                                "'{}' needs {}, moving it to '{}' would be breaking",
+                                raw, too_new[raw], version
+                            )
+                        }));
+                        (prereleases.is_empty() && needs_newer_rust.is_empty())
+                            .then_some((Some(version), Some(member_lists_map)))
+                    }
+                    Resolution::Conflict(reason) => {
+                        conflict_types.push(ConflictType::VersionResolution);
+                        diagnostics.extend(reason);
+                        None
+                    }
+                }
+            }
+            (crate::ExactPinPolicy::Win, [pin]) => {
+                Some((Some(pin.clone()), Some(member_lists_map)))
+            }
+            _ => {
+                conflict_types.push(ConflictType::ExactPin);
                 None
             }
         }
@@ -484,24 +1224,48 @@ fn process_dependency(
 
     // Check for default-features conflicts
     let df_values: Vec<bool> = if let Some((ref version, _)) = version_resolution {
-        tracker.get_default_features_for_version(version)
+        tracker.get_default_features_for_version(version.as_deref())
     } else {
-        tracker.version_specs.keys().map(|(_, df)| *df).collect()
+        tracker
+            .version_specs
+            .keys()
+            .map(|spec| spec.default_features)
+            .collect()
     };
 
     let unique: std::collections::HashSet<_> = df_values.iter().copied().collect();
+    let mut default_features_resolved_from = None;
     if unique.len() > 1 {
-        conflict_types.push(ConflictType::DefaultFeatures);
+        // Disabling defaults in the workspace entry would silently drop them for
+        // members already inheriting it, so only rewrite when all users are consolidated now
+        let can_disable = *options.default_features_strategy
+            == crate::DefaultFeaturesStrategy::Disable
+            && !tracker.has_workspace_refs;
+        match (can_disable, &version_resolution) {
+            (true, Some((version, _))) => {
+                default_features_resolved_from =
+                    Some(tracker.build_default_features_map(version.as_deref()));
+            }
+            _ => conflict_types.push(ConflictType::DefaultFeatures),
+        }
+    }
+
+    // Git dependencies must agree on the branch, tag or revision
+    if tracker.unique_git_references().len() > 1 {
+        conflict_types.push(ConflictType::GitReference);
     }
 
     // If any conflicts found, return error
     if !conflict_types.is_empty() {
-        return Err(create_conflict(key, &tracker.version_specs, conflict_types));
+        let mut conflict = create_conflict(key, &tracker.version_specs, conflict_types);
+        conflict.diagnostics = diagnostics;
+        return Err(conflict);
     }
 
     // Extract resolved version (we know it exists because no conflicts)
     let (resolved_version, resolved_from) = version_resolution.unwrap();
-    let common_default_features = get_common_default_features(&df_values);
+    let common_default_features =
+        default_features_resolved_from.is_none() && get_common_default_features(&df_values);
 
     // Count unique members (a member may appear multiple times with different sections)
     let unique_member_count = all_members
@@ -510,7 +1274,22 @@ fn process_dependency(
         .collect::<std::collections::HashSet<_>>()
         .len();
 
-    if should_consolidate(has_workspace, unique_member_count, min_members) {
+    let members_moved = resolved_from.as_ref().map_or(0, |version_map| {
+        version_map
+            .iter()
+            .filter(|(version, _)| Some(*version) != resolved_version.as_ref())
+            .flat_map(|(_, members)| members)
+            .filter(|member| *member != WORKSPACE_MARKER)
+            .collect::<std::collections::HashSet<_>>()
+            .len()
+    });
+
+    // Every member a sibling depends on by path gets an entry, whatever the threshold
+    if should_consolidate(
+        has_workspace || key.path.is_some(),
+        unique_member_count,
+        min_members,
+    ) {
         Ok(Some(CommonDependency {
             name: key.name.clone(),
             version: resolved_version,
@@ -518,13 +1297,52 @@ fn process_dependency(
             package: key.package.clone(),
             registry: key.registry.clone(),
             default_features: common_default_features,
+            path: key.path.clone(),
+            git: tracker.git_source(),
+            features: Vec::new(),
+            resolution_override: resolved_from
+                .is_some()
+                .then(|| options.override_for(key).copied())
+                .flatten()
+                .filter(|strategy| {
+                    options.version_resolver.is_some()
+                        || strategy != options.version_resolution_strategy
+                }),
+            members_moved,
+            pins: resolved_from
+                .iter()
+                .flatten()
+                .filter(|(version, _)| crate::version_resolver::is_exact_pin(version))
+                .map(|(version, members)| (version.clone(), members.clone()))
+                .collect(),
+            diverging: HashMap::new(),
+            member_changes: BTreeMap::new(),
             resolved_from,
+            default_features_resolved_from,
         }))
     } else {
         Ok(None)
     }
 }
 
+impl CommonDependency {
+    /// Classify how each member's requirement changes when it moves to the workspace version
+    pub(crate) fn classify_member_changes(&mut self) {
+        let (Some(resolved_from), Some(version)) = (&self.resolved_from, &self.version) else {
+            return;
+        };
+        for (original, members) in resolved_from {
+            let Some(change) = classify_change(original, version) else {
+                continue;
+            };
+            for member in members.iter().filter(|m| *m != WORKSPACE_MARKER) {
+                let entry = self.member_changes.entry(member.clone()).or_insert(change);
+                *entry = (*entry).max(change);
+            }
+        }
+    }
+}
+
 /// Find workspace dependencies that are not used by any member
 fn find_unused_workspace_deps(
     common_deps: &[CommonDependency],
@@ -550,20 +1368,21 @@ fn find_unused_workspace_deps(
 
 fn create_conflict(
     key: &WorkspaceDepKey,
-    version_spec_map: &HashMap<(String, bool), VersionUsage>,
+    version_spec_map: &HashMap<SpecKey, VersionUsage>,
     conflict_types: Vec<ConflictType>,
 ) -> ConflictingDependency {
     let version_specs = version_spec_map
         .iter()
-        .map(|((version, default_features), usage)| {
+        .map(|(spec, usage)| {
             let mut members: Vec<String> =
                 usage.members.iter().map(|(name, _)| name.clone()).collect();
             if usage.in_workspace {
                 members.push(WORKSPACE_MARKER.to_string());
             }
             VersionSpec {
-                version: version.clone(),
-                default_features: *default_features,
+                version: spec.version.clone(),
+                default_features: spec.default_features,
+                git_reference: spec.git_reference.clone(),
                 members,
             }
         })
@@ -572,6 +1391,7 @@ fn create_conflict(
         name: key.name.clone(),
         version_specs,
         conflict_types,
+        diagnostics: Vec::new(),
     }
 }
 
@@ -580,42 +1400,126 @@ pub(crate) fn analyze_workspace(
     data: &WorkspaceData,
     exclude: &[String],
     min_members: usize,
-    resolution_strategy: &crate::VersionResolutionStrategy,
+    hoist_features: bool,
+    options: &ResolutionOptions,
 ) -> Result<DependencyAnalysis> {
     let mut dep_trackers: HashMap<WorkspaceDepKey, DependencyTracker> = HashMap::new();
 
     track_member_dependencies(&mut dep_trackers, &data.member_deps);
     track_workspace_dependencies(&mut dep_trackers, &data.workspace_deps);
+    for (key, tracker) in dep_trackers.iter_mut() {
+        tracker.has_workspace_refs = data
+            .workspace_refs
+            .iter()
+            .any(|(name, _)| name == &key.name);
+    }
 
     // Process each tracked dependency
     let mut common_deps = Vec::new();
     let mut conflicts = Vec::new();
+    // Sources that would write [workspace.dependencies] entries, by name
+    let mut sources: HashMap<String, Vec<(WorkspaceDepKey, Vec<VersionSpec>)>> = HashMap::new();
 
-    for (key, tracker) in dep_trackers {
+    for (key, mut tracker) in dep_trackers {
         if exclude.contains(&key.name) {
             continue;
         }
 
+        // Pinned members keep their explicit version instead of joining the workspace entry
+        let excluded_pins = if *options.exact_pin_policy == crate::ExactPinPolicy::ExcludeMember
+            && tracker.version_count() > 1
+        {
+            tracker.remove_exact_pins()
+        } else {
+            HashMap::new()
+        };
+        if tracker.version_specs.is_empty() {
+            continue;
+        }
+
         let has_workspace = tracker.has_workspace();
         let all_members = tracker.all_members();
+        let specs = create_conflict(&key, &tracker.version_specs, Vec::new()).version_specs;
 
         // Process dependency
-        let result = process_dependency(
+        let mut result = process_dependency(
             &key,
             &tracker,
             has_workspace,
             &all_members,
             min_members,
-            resolution_strategy,
+            options,
         );
 
+        // Members outside the largest compatible group keep their explicit version,
+        // the conflict is only reported if the rest still cannot be consolidated
+        let mut diverging = HashMap::new();
+        if options.partial_consolidation
+            && let Err(conflict) = &result
+            && conflict.conflict_types == [ConflictType::VersionResolution]
+        {
+            let mut group = tracker.clone();
+            let outliers = group.remove_diverging_members();
+            if !outliers.is_empty()
+                && let Ok(Some(dep)) = process_dependency(
+                    &key,
+                    &group,
+                    group.has_workspace(),
+                    &group.all_members(),
+                    min_members,
+                    options,
+                )
+            {
+                result = Ok(Some(dep));
+                tracker = group;
+                diverging = outliers;
+            }
+        }
+
+        if has_workspace || matches!(result, Ok(Some(_))) {
+            sources
+                .entry(key.name.clone())
+                .or_default()
+                .push((key.clone(), specs));
+        }
+
         match result {
-            Ok(Some(dep)) => common_deps.push(dep),
+            Ok(Some(mut dep)) => {
+                // Members already inheriting the entry would pick up hoisted features,
+                // so only hoist when every user is being consolidated now
+                if hoist_features && !tracker.has_workspace_refs {
+                    dep.features = tracker.common_features();
+                }
+                dep.pins.extend(excluded_pins);
+                dep.diverging = diverging;
+                common_deps.push(dep);
+            }
             Ok(None) => {} // Doesn't meet consolidation conditions
             Err(conflict) => conflicts.push(conflict),
         }
     }
 
+    // One name can only have one workspace entry, so members of every source keep
+    // their explicit spec when several sources would write it
+    for (name, mut claims) in sources {
+        if claims.len() < 2 {
+            continue;
+        }
+        common_deps.retain(|dep| dep.name != name);
+        claims.sort_by_key(|(key, _)| key.source());
+        let described: Vec<String> = claims.iter().map(|(key, _)| key.source()).collect();
+        conflicts.push(ConflictingDependency {
+            diagnostics: vec![format!(
+                "{} comes from more than one source: {}",
+                name,
+                described.join(", ")
+            )],
+            name,
+            version_specs: claims.into_iter().flat_map(|(_, specs)| specs).collect(),
+            conflict_types: vec![ConflictType::GitReference],
+        });
+    }
+
     // Find unused workspace dependencies
     let unused_workspace_deps =
         find_unused_workspace_deps(&common_deps, &data.workspace_refs, &data.workspace_deps);
@@ -624,9 +1528,45 @@ pub(crate) fn analyze_workspace(
         common_deps,
         conflicts,
         unused_workspace_deps,
+        demoted_deps: Vec::new(),
+        skipped: data.skipped.clone(),
     })
 }
 
+/// Demote workspace dependencies used by fewer than `min_members` members, counting
+/// members about to be consolidated as users. Those members keep their explicit spec
+/// `can_demote` rules out dependencies that must not be touched
+pub(crate) fn demote_rarely_used(
+    analysis: &mut DependencyAnalysis,
+    data: &WorkspaceData,
+    min_members: usize,
+    can_demote: impl Fn(&str) -> bool,
+) {
+    let mut names: Vec<&String> = data.workspace_deps.keys().collect();
+    names.sort();
+
+    for name in names {
+        let Some(inheriting) = data.workspace_ref_members.get(name) else {
+            continue; // Unused, which is what --prune-unused is for
+        };
+        let consolidating = analysis
+            .common_deps
+            .iter()
+            .filter(|dep| &dep.name == name)
+            .flat_map(|dep| dep.members.iter().map(|(member, _)| member));
+        let users: BTreeSet<&String> = inheriting.iter().chain(consolidating).collect();
+        if users.len() >= min_members || !can_demote(name) {
+            continue;
+        }
+
+        analysis.common_deps.retain(|dep| &dep.name != name);
+        analysis.demoted_deps.push(DemotedDependency {
+            name: name.clone(),
+            members: inheriting.iter().cloned().collect(),
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -655,14 +1595,17 @@ version = "0.1.0"
 [dependencies]
 serde = "1.0"
 "#,
-        vec![DepSection::Dependencies],
+        vec![DepKind::Dependencies],
         vec![DependencySpec {
             name: "serde".into(),
-            version: "1.0".into(),
-            section: DepSection::Dependencies,
+            version: Some("1.0".into()),
+            section: DepKind::Dependencies.into(),
             package: None,
             registry: None,
             default_features: true,
+            path: None,
+            git: None,
+            features: vec![],
         }]
     )]
     #[case::inline_table_version(
@@ -670,14 +1613,17 @@ serde = "1.0"
 [dependencies]
 serde = { version = "1.0" }
 "#,
-        vec![DepSection::Dependencies],
+        vec![DepKind::Dependencies],
         vec![DependencySpec {
             name: "serde".into(),
-            version: "1.0".into(),
-            section: DepSection::Dependencies,
+            version: Some("1.0".into()),
+            section: DepKind::Dependencies.into(),
             package: None,
             registry: None,
             default_features: true,
+            path: None,
+            git: None,
+            features: vec![],
         }]
     )]
     #[case::table_format_version(
@@ -685,14 +1631,17 @@ serde = { version = "1.0" }
 [dependencies.serde]
 version = "1.0"
 "#,
-        vec![DepSection::Dependencies],
+        vec![DepKind::Dependencies],
         vec![DependencySpec {
             name: "serde".into(),
-            version: "1.0".into(),
-            section: DepSection::Dependencies,
+            version: Some("1.0".into()),
+            section: DepKind::Dependencies.into(),
             package: None,
             registry: None,
             default_features: true,
+            path: None,
+            git: None,
+            features: vec![],
         }]
     )]
     #[case::multiple_dependencies(
@@ -702,31 +1651,40 @@ serde = "1.0"
 anyhow = "1.0"
 tokio = { version = "1.0" }
 "#,
-        vec![DepSection::Dependencies],
+        vec![DepKind::Dependencies],
         vec![
             DependencySpec {
                 name: "serde".into(),
-                version: "1.0".into(),
-                section: DepSection::Dependencies,
+                version: Some("1.0".into()),
+                section: DepKind::Dependencies.into(),
                 package: None,
                 registry: None,
                 default_features: true,
+                path: None,
+                git: None,
+                features: vec![],
             },
             DependencySpec {
                 name: "anyhow".into(),
-                version: "1.0".into(),
-                section: DepSection::Dependencies,
+                version: Some("1.0".into()),
+                section: DepKind::Dependencies.into(),
                 package: None,
                 registry: None,
                 default_features: true,
+                path: None,
+                git: None,
+                features: vec![],
             },
             DependencySpec {
                 name: "tokio".into(),
-                version: "1.0".into(),
-                section: DepSection::Dependencies,
+                version: Some("1.0".into()),
+                section: DepKind::Dependencies.into(),
                 package: None,
                 registry: None,
                 default_features: true,
+                path: None,
+                git: None,
+                features: vec![],
             },
         ]
     )]
@@ -735,14 +1693,17 @@ tokio = { version = "1.0" }
 [dependencies]
 serde_crate = { package = "serde", version = "1.0" }
 "#,
-        vec![DepSection::Dependencies],
+        vec![DepKind::Dependencies],
         vec![DependencySpec {
             name: "serde_crate".into(),
-            version: "1.0".into(),
-            section: DepSection::Dependencies,
+            version: Some("1.0".into()),
+            section: DepKind::Dependencies.into(),
             package: Some("serde".into()),
             registry: None,
             default_features: true,
+            path: None,
+            git: None,
+            features: vec![],
         }]
     )]
     #[case::custom_registry(
@@ -750,14 +1711,17 @@ serde_crate = { package = "serde", version = "1.0" }
 [dependencies]
 my_crate = { version = "1.0", registry = "my-registry" }
 "#,
-        vec![DepSection::Dependencies],
+        vec![DepKind::Dependencies],
         vec![DependencySpec {
             name: "my_crate".into(),
-            version: "1.0".into(),
-            section: DepSection::Dependencies,
+            version: Some("1.0".into()),
+            section: DepKind::Dependencies.into(),
             package: None,
             registry: Some("my-registry".into()),
             default_features: true,
+            path: None,
+            git: None,
+            features: vec![],
         }]
     )]
     #[case::dev_dependencies(
@@ -765,14 +1729,17 @@ my_crate = { version = "1.0", registry = "my-registry" }
 [dev-dependencies]
 rstest = "0.23"
 "#,
-        vec![DepSection::DevDependencies],
+        vec![DepKind::DevDependencies],
         vec![DependencySpec {
             name: "rstest".into(),
-            version: "0.23".into(),
-            section: DepSection::DevDependencies,
+            version: Some("0.23".into()),
+            section: DepKind::DevDependencies.into(),
             package: None,
             registry: None,
             default_features: true,
+            path: None,
+            git: None,
+            features: vec![],
         }]
     )]
     #[case::build_dependencies(
@@ -780,14 +1747,17 @@ rstest = "0.23"
 [build-dependencies]
 cc = "1.0"
 "#,
-        vec![DepSection::BuildDependencies],
+        vec![DepKind::BuildDependencies],
         vec![DependencySpec {
             name: "cc".into(),
-            version: "1.0".into(),
-            section: DepSection::BuildDependencies,
+            version: Some("1.0".into()),
+            section: DepKind::BuildDependencies.into(),
             package: None,
             registry: None,
             default_features: true,
+            path: None,
+            git: None,
+            features: vec![],
         }]
     )]
     #[case::multiple_sections(
@@ -801,31 +1771,40 @@ rstest = "0.23"
 [build-dependencies]
 cc = "1.0"
 "#,
-        vec![DepSection::Dependencies, DepSection::DevDependencies, DepSection::BuildDependencies],
+        vec![DepKind::Dependencies, DepKind::DevDependencies, DepKind::BuildDependencies],
         vec![
             DependencySpec {
                 name: "serde".into(),
-                version: "1.0".into(),
-                section: DepSection::Dependencies,
+                version: Some("1.0".into()),
+                section: DepKind::Dependencies.into(),
                 package: None,
                 registry: None,
                 default_features: true,
+                path: None,
+                git: None,
+                features: vec![],
             },
             DependencySpec {
                 name: "rstest".into(),
-                version: "0.23".into(),
-                section: DepSection::DevDependencies,
+                version: Some("0.23".into()),
+                section: DepKind::DevDependencies.into(),
                 package: None,
                 registry: None,
                 default_features: true,
+                path: None,
+                git: None,
+                features: vec![],
             },
             DependencySpec {
                 name: "cc".into(),
-                version: "1.0".into(),
-                section: DepSection::BuildDependencies,
+                version: Some("1.0".into()),
+                section: DepKind::BuildDependencies.into(),
                 package: None,
                 registry: None,
                 default_features: true,
+                path: None,
+                git: None,
+                features: vec![],
             },
         ]
     )]
@@ -835,58 +1814,118 @@ cc = "1.0"
 serde = { workspace = true }
 anyhow = "1.0"
 "#,
-        vec![DepSection::Dependencies],
+        vec![DepKind::Dependencies],
         vec![DependencySpec {
             name: "anyhow".into(),
-            version: "1.0".into(),
-            section: DepSection::Dependencies,
+            version: Some("1.0".into()),
+            section: DepKind::Dependencies.into(),
             package: None,
             registry: None,
             default_features: true,
+            path: None,
+            git: None,
+            features: vec![],
         }]
     )]
-    #[case::path_deps_skipped(
+    #[case::path_dependency(
         r#"
 [dependencies]
 my_local = { path = "../my-local" }
 serde = "1.0"
 "#,
-        vec![DepSection::Dependencies],
-        vec![DependencySpec {
-            name: "serde".into(),
-            version: "1.0".into(),
-            section: DepSection::Dependencies,
-            package: None,
-            registry: None,
-            default_features: true,
-        }]
+        vec![DepKind::Dependencies],
+        vec![
+            DependencySpec {
+                name: "my_local".into(),
+                version: None,
+                section: DepKind::Dependencies.into(),
+                package: None,
+                registry: None,
+                default_features: true,
+                path: Some("../my-local".into()),
+                git: None,
+                features: vec![],
+            },
+            DependencySpec {
+                name: "serde".into(),
+                version: Some("1.0".into()),
+                section: DepKind::Dependencies.into(),
+                package: None,
+                registry: None,
+                default_features: true,
+                path: None,
+                git: None,
+                features: vec![],
+            },
+        ]
     )]
-    #[case::git_deps_skipped(
+    #[case::git_dependency(
         r#"
 [dependencies]
 my_git = { git = "https://github.com/example/repo" }
 serde = "1.0"
 "#,
-        vec![DepSection::Dependencies],
+        vec![DepKind::Dependencies],
+        vec![
+            DependencySpec {
+                name: "my_git".into(),
+                version: None,
+                section: DepKind::Dependencies.into(),
+                package: None,
+                registry: None,
+                default_features: true,
+                path: None,
+                git: Some(GitSource {
+                    url: "https://github.com/example/repo".into(),
+                    reference: None,
+                }),
+                features: vec![],
+            },
+            DependencySpec {
+                name: "serde".into(),
+                version: Some("1.0".into()),
+                section: DepKind::Dependencies.into(),
+                package: None,
+                registry: None,
+                default_features: true,
+                path: None,
+                git: None,
+                features: vec![],
+            },
+        ]
+    )]
+    #[case::git_dependency_with_rev(
+        r#"
+[dependencies.my_git]
+git = "https://github.com/example/repo"
+rev = "abc123"
+"#,
+        vec![DepKind::Dependencies],
         vec![DependencySpec {
-            name: "serde".into(),
-            version: "1.0".into(),
-            section: DepSection::Dependencies,
+            name: "my_git".into(),
+            version: None,
+            section: DepKind::Dependencies.into(),
             package: None,
             registry: None,
             default_features: true,
+            path: None,
+            git: Some(GitSource {
+                url: "https://github.com/example/repo".into(),
+                reference: Some(GitReference::Rev("abc123".into())),
+            }),
+            features: vec![],
         }]
     )]
     #[case::empty_section(
         r#"
 [dependencies]
 "#,
-        vec![DepSection::Dependencies],
+        vec![DepKind::Dependencies],
         vec![]
     )]
     #[case::missing_section(
         "",
-        vec![DepSection::Dependencies],
+        vec![DepKind::Dependencies],
         vec![]
     )]
     #[case::version_with_features(
@@ -894,14 +1933,17 @@ serde = "1.0"
 [dependencies]
 serde = { version = "1.0", features = ["derive"] }
 "#,
-        vec![DepSection::Dependencies],
+        vec![DepKind::Dependencies],
         vec![DependencySpec {
             name: "serde".into(),
-            version: "1.0".into(),
-            section: DepSection::Dependencies,
+            version: Some("1.0".into()),
+            section: DepKind::Dependencies.into(),
             package: None,
             registry: None,
             default_features: true,
+            path: None,
+            git: None,
+            features: vec!["derive".into()],
         }]
     )]
     #[case::version_with_optional(
@@ -909,14 +1951,17 @@ serde = { version = "1.0", features = ["derive"] }
 [dependencies]
 serde = { version = "1.0", optional = true }
 "#,
-        vec![DepSection::Dependencies],
+        vec![DepKind::Dependencies],
         vec![DependencySpec {
             name: "serde".into(),
-            version: "1.0".into(),
-            section: DepSection::Dependencies,
+            version: Some("1.0".into()),
+            section: DepKind::Dependencies.into(),
             package: None,
             registry: None,
             default_features: true,
+            path: None,
+            git: None,
+            features: vec![],
         }]
     )]
     #[case::version_with_default_features(
@@ -924,14 +1969,17 @@ serde = { version = "1.0", optional = true }
 [dependencies]
 serde = { version = "1.0", default-features = false }
 "#,
-        vec![DepSection::Dependencies],
+        vec![DepKind::Dependencies],
         vec![DependencySpec {
             name: "serde".into(),
-            version: "1.0".into(),
-            section: DepSection::Dependencies,
+            version: Some("1.0".into()),
+            section: DepKind::Dependencies.into(),
             package: None,
             registry: None,
             default_features: false,
+            path: None,
+            git: None,
+            features: vec![],
         }]
     )]
     #[case::complex_dependency(
@@ -939,40 +1987,135 @@ serde = { version = "1.0", default-features = false }
 [dependencies]
 my_crate = { package = "real-crate", version = "2.0", registry = "custom", features = ["async"], optional = true }
 "#,
-        vec![DepSection::Dependencies],
+        vec![DepKind::Dependencies],
         vec![DependencySpec {
             name: "my_crate".into(),
-            version: "2.0".into(),
-            section: DepSection::Dependencies,
+            version: Some("2.0".into()),
+            section: DepKind::Dependencies.into(),
             package: Some("real-crate".into()),
             registry: Some("custom".into()),
             default_features: true,
+            path: None,
+            git: None,
+            features: vec!["async".into()],
         }]
     )]
-    #[case::path_and_version_skipped(
+    #[case::path_and_version(
         r#"
 [dependencies]
 my_crate = { path = "../local", version = "1.0" }
 "#,
-        vec![DepSection::Dependencies],
-        vec![]
+        vec![DepKind::Dependencies],
+        vec![DependencySpec {
+            name: "my_crate".into(),
+            version: Some("1.0".into()),
+            section: DepKind::Dependencies.into(),
+            package: None,
+            registry: None,
+            default_features: true,
+            path: Some("../local".into()),
+            git: None,
+            features: vec![],
+        }]
     )]
-    #[case::git_with_version_skipped(
+    #[case::git_with_version(
         r#"
 [dependencies]
-my_crate = { git = "https://github.com/example/repo", version = "1.0" }
+my_crate = { git = "https://github.com/example/repo", version = "1.0", tag = "v1.0.0" }
 "#,
-        vec![DepSection::Dependencies],
-        vec![]
+        vec![DepKind::Dependencies],
+        vec![DependencySpec {
+            name: "my_crate".into(),
+            version: Some("1.0".into()),
+            section: DepKind::Dependencies.into(),
+            package: None,
+            registry: None,
+            default_features: true,
+            path: None,
+            git: Some(GitSource {
+                url: "https://github.com/example/repo".into(),
+                reference: Some(GitReference::Tag("v1.0.0".into())),
+            }),
+            features: vec![],
+        }]
+    )]
+    #[case::target_dependencies(
+        r#"
+[dependencies]
+serde = "1.0"
+
+[target.'cfg(unix)'.dependencies]
+libc = "0.2"
+
+[target.x86_64-pc-windows-msvc.dependencies]
+windows-sys = { version = "0.52" }
+"#,
+        vec![DepKind::Dependencies],
+        vec![
+            DependencySpec {
+                name: "serde".into(),
+                version: Some("1.0".into()),
+                section: DepKind::Dependencies.into(),
+                package: None,
+                registry: None,
+                default_features: true,
+                path: None,
+                git: None,
+                features: vec![],
+            },
+            DependencySpec {
+                name: "libc".into(),
+                version: Some("0.2".into()),
+                section: DepSection::for_target(DepKind::Dependencies, "cfg(unix)"),
+                package: None,
+                registry: None,
+                default_features: true,
+                path: None,
+                git: None,
+                features: vec![],
+            },
+            DependencySpec {
+                name: "windows-sys".into(),
+                version: Some("0.52".into()),
+                section: DepSection::for_target(DepKind::Dependencies, "x86_64-pc-windows-msvc"),
+                package: None,
+                registry: None,
+                default_features: true,
+                path: None,
+                git: None,
+                features: vec![],
+            },
+        ]
+    )]
+    #[case::target_section_filtered_by_kind(
+        r#"
+[target.'cfg(unix)'.dev-dependencies]
+nix = "0.29"
+
+[target.'cfg(unix)'.build-dependencies]
+cc = "1.0"
+"#,
+        vec![DepKind::BuildDependencies],
+        vec![DependencySpec {
+            name: "cc".into(),
+            version: Some("1.0".into()),
+            section: DepSection::for_target(DepKind::BuildDependencies, "cfg(unix)"),
+            package: None,
+            registry: None,
+            default_features: true,
+            path: None,
+            git: None,
+            features: vec![],
+        }]
     )]
     fn test_parse_dependencies(
         #[case] toml_content: &str,
-        #[case] sections: Vec<DepSection>,
+        #[case] kinds: Vec<DepKind>,
         #[case] expected: Vec<DependencySpec>,
     ) -> Result<()> {
         let (_temp_dir, manifest_path) = create_test_manifest(toml_content)?;
 
-        let parsed = parse_dependencies(&manifest_path, &sections)?;
+        let parsed = parse_dependencies(&manifest_path, &kinds)?;
 
         // Sort both vectors by name for consistent comparison
         let mut deps = parsed.explicit_deps;
@@ -989,10 +2132,47 @@ my_crate = { git = "https://github.com/example/repo", version = "1.0" }
         Ok(())
     }
 
+    #[rstest]
+    #[case::https("https://github.com/example/repo")]
+    #[case::trailing_git("https://github.com/example/repo.git")]
+    #[case::trailing_slash("https://github.com/example/repo/")]
+    #[case::trailing_git_and_slash("https://github.com/example/repo.git/")]
+    #[case::scp_like("git@github.com:example/repo.git")]
+    #[case::ssh("ssh://git@github.com/example/repo")]
+    #[case::uppercase_host("https://GitHub.com/example/repo")]
+    fn test_normalize_git_url(#[case] url: &str) {
+        assert_eq!(normalize_git_url(url), "github.com/example/repo");
+    }
+
+    #[test]
+    fn test_normalize_git_url_keeps_distinct_repos() {
+        assert_ne!(
+            normalize_git_url("https://github.com/example/repo"),
+            normalize_git_url("https://github.com/fork/repo")
+        );
+    }
+
+    #[rstest]
+    #[case::https_over_more_common_ssh(
+        &["ssh://git@github.com/example/repo.git", "ssh://git@github.com/example/repo.git", "https://github.com/example/repo"],
+        "https://github.com/example/repo"
+    )]
+    #[case::most_common_https(
+        &["https://github.com/example/repo", "https://github.com/example/repo.git", "https://github.com/example/repo.git"],
+        "https://github.com/example/repo.git"
+    )]
+    fn test_git_source_url(#[case] urls: &[&str], #[case] expected: &str) {
+        let mut tracker = DependencyTracker::default();
+        for url in urls {
+            *tracker.git_urls.entry(url.to_string()).or_default() += 1;
+        }
+        assert_eq!(tracker.git_source().unwrap().url, expected);
+    }
+
     #[test]
     fn test_invalid_toml() {
         let (_temp_dir, manifest_path) = create_test_manifest("not valid toml [[[").unwrap();
-        let result = parse_dependencies(&manifest_path, &[DepSection::Dependencies]);
+        let result = parse_dependencies(&manifest_path, &[DepKind::Dependencies]);
         let err = result.unwrap_err();
         assert!(err.to_string().contains("Failed to parse TOML"));
     }
@@ -1001,7 +2181,7 @@ my_crate = { git = "https://github.com/example/repo", version = "1.0" }
     fn test_missing_file() {
         let result = parse_dependencies(
             std::path::Path::new("/nonexistent/path/Cargo.toml"),
-            &[DepSection::Dependencies],
+            &[DepKind::Dependencies],
         );
         let err = result.unwrap_err();
         assert!(err.to_string().contains("Failed to read"));
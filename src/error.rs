@@ -4,6 +4,10 @@ use std::fmt;
 pub enum CheckFailure {
     Consolidation(usize),
     Conflicts(usize),
+    /// [workspace.dependencies] entries no member uses, only checked with pruning enabled
+    UnusedDependencies(usize),
+    /// [workspace.dependencies] entries used by too few members, only checked in demote mode
+    Demotion(usize),
 }
 
 impl fmt::Display for CheckFailure {
@@ -19,6 +23,16 @@ impl fmt::Display for CheckFailure {
             CheckFailure::Conflicts(count) => {
                 write!(f, "Check failed: {} unresolved conflicts", count)
             }
+            CheckFailure::UnusedDependencies(count) => {
+                write!(f, "Check failed: {} unused workspace dependencies", count)
+            }
+            CheckFailure::Demotion(count) => {
+                write!(
+                    f,
+                    "Check failed: {} workspace dependencies could be demoted",
+                    count
+                )
+            }
         }
     }
 }
@@ -1,16 +1,36 @@
+mod config_file;
 mod dependency;
 mod error;
+mod lints;
+mod lockfile;
+mod manifest;
 mod output_format;
+mod package;
+mod registry;
 mod toml_editor;
 mod version_resolver;
 mod workspace;
 
+pub use config_file::ProjectConfig;
 pub use error::CheckFailure;
+pub use version_resolver::{DependencyKey, Resolution, VersionResolver};
 
 use anyhow::{Context, Result};
-use dependency::{DepSection, analyze_workspace, parse_workspace_data};
-use toml_editor::{update_member_dependencies, update_workspace_dependencies};
-use workspace::discover_workspace;
+use dependency::{
+    ALL_KINDS, DepKind, ResolutionOptions, analyze_workspace, collect_rust_versions,
+    demote_rarely_used, parse_dependencies, parse_workspace_data,
+};
+use lints::{LintAnalysis, analyze_lints};
+use lockfile::Lockfile;
+use package::{PackageAnalysis, analyze_package_fields};
+use registry::RegistryIndex;
+use toml_editor::{
+    InlinedDependency, inline_member_dependencies, remove_workspace_dependencies,
+    update_member_dependencies, update_member_lints, update_member_package,
+    update_workspace_dependencies, update_workspace_lints, update_workspace_package,
+    workspace_dependency_entries,
+};
+use workspace::{WorkspaceInfo, discover_workspace};
 
 #[derive(Clone, Debug, Copy, PartialEq, Eq, clap::ValueEnum)]
 pub enum VersionResolutionStrategy {
@@ -22,10 +42,100 @@ pub enum VersionResolutionStrategy {
     HighestCompatible,
     /// Use the lowest version
     Lowest,
+    /// Use the version Cargo.lock resolves to, falling back to highest-compatible
+    /// when the lockfile has several (or no) versions of the crate
+    Lockfile,
+    /// Use the intersection of all requirements, written as the tightest
+    /// requirement that satisfies every member
+    Intersect,
+    /// Use the newest version in the local registry index that satisfies every requirement,
+    /// falling back to highest-compatible when the index does not know the crate
+    LatestCompatible,
+    /// Use the version most members already use (the workspace entry counts as a vote),
+    /// breaking ties with highest-compatible
+    Majority,
     /// Fail on version conflicts
     Fail,
 }
 
+/// Version resolution strategy for dependencies whose name matches a glob pattern
+/// Parsed from `PATTERN=STRATEGY`, e.g. `rustls=fail` or `openssl*=fail`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionResolutionOverride {
+    pub pattern: glob::Pattern,
+    pub strategy: VersionResolutionStrategy,
+}
+
+impl std::str::FromStr for VersionResolutionOverride {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (pattern, strategy) = s
+            .split_once('=')
+            .ok_or_else(|| format!("Expected PATTERN=STRATEGY, got '{}'", s))?;
+        VersionResolutionOverride::new(pattern.trim(), strategy.trim())
+    }
+}
+
+impl VersionResolutionOverride {
+    pub(crate) fn new(pattern: &str, strategy: &str) -> std::result::Result<Self, String> {
+        use clap::ValueEnum;
+
+        let pattern = glob::Pattern::new(pattern)
+            .map_err(|e| format!("Invalid glob pattern '{}': {}", pattern, e))?;
+        let strategy = VersionResolutionStrategy::from_str(strategy, false)
+            .map_err(|_| format!("Invalid version resolution strategy '{}'", strategy))?;
+        Ok(VersionResolutionOverride { pattern, strategy })
+    }
+}
+
+#[derive(Clone, Debug, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum DefaultFeaturesStrategy {
+    /// Skip dependencies with conflicting default-features
+    Skip,
+    /// Disable default features in the workspace entry and
+    /// add `features = ["default"]` to members that want them
+    Disable,
+}
+
+/// How prerelease requirements (e.g. "1.0.0-alpha") are treated during resolution
+#[derive(Clone, Debug, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PrereleasePolicy {
+    /// Treat prereleases like any other version
+    Allow,
+    /// Resolve using stable versions when any member uses one
+    PreferStable,
+    /// Never write a prerelease to the workspace, reporting dependencies
+    /// that only use prereleases as conflicts
+    Deny,
+}
+
+/// How exact pins (`=x.y.z`) are treated when members disagree on a version
+#[derive(Clone, Debug, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExactPinPolicy {
+    /// Report the dependency as a conflict
+    Conflict,
+    /// Use the pin for the whole workspace
+    Win,
+    /// Leave pinned members out of consolidation, keeping their explicit version
+    ExcludeMember,
+}
+
+/// How bare version requirements are written to [workspace.dependencies]
+/// Components needed to keep 0.x requirements compatible are never dropped
+#[derive(Clone, Debug, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum VersionFormat {
+    /// Keep the precision members wrote, e.g. "1.0"
+    AsWritten,
+    /// Major version only, e.g. "1"
+    Major,
+    /// Major and minor version, e.g. "1.0"
+    #[value(name = "major.minor")]
+    MajorMinor,
+    /// Full version, e.g. "1.0.0"
+    Full,
+}
+
 #[derive(Clone, Debug, Copy, PartialEq, Eq, clap::ValueEnum)]
 pub enum OutputFormat {
     Text,
@@ -45,10 +155,131 @@ pub struct Config {
     pub exclude_members: Vec<glob::Pattern>,
     pub check: bool,
     pub version_resolution_strategy: VersionResolutionStrategy,
+    /// Per-dependency strategies, taking precedence over `workspace-deps.toml`
+    pub version_resolution_overrides: Vec<VersionResolutionOverride>,
+    /// Custom resolver used instead of `version_resolution_strategy`,
+    /// per-dependency overrides still take precedence
+    pub version_resolver: Option<Box<dyn VersionResolver>>,
+    pub prerelease_policy: PrereleasePolicy,
+    pub exact_pin_policy: ExactPinPolicy,
+    /// Skip versions whose rust-version is newer than the workspace or member
+    /// rust-version, read from the local registry index cache
+    pub respect_rust_version: bool,
+    /// Local registry directory to read crate versions from instead of the
+    /// index cache under `$CARGO_HOME`
+    pub registry_index: Option<std::path::PathBuf>,
+    /// Refuse to apply changes that move a member to a SemVer-incompatible version
+    pub deny_breaking_changes: bool,
+    /// Consolidate the largest group of SemVer-compatible members when versions conflict,
+    /// leaving the other members on their explicit versions
+    pub partial_consolidation: bool,
+    pub default_features_strategy: DefaultFeaturesStrategy,
+    /// How versions are written to [workspace.dependencies]
+    pub version_format: VersionFormat,
+    /// Hoist `path` dependencies on other workspace members into [workspace.dependencies]
+    pub consolidate_path_dependencies: bool,
+    /// Move features requested by every member into the [workspace.dependencies] entry
+    pub hoist_features: bool,
+    /// Remove [workspace.dependencies] entries no member uses, along with their comments
+    pub prune_unused: bool,
+    /// Move [workspace.dependencies] entries used by fewer than `min_members` members
+    /// back into those members as explicit specs
+    pub demote: bool,
+    /// Also consolidate shared [package] fields into [workspace.package]
+    pub process_package: bool,
+    /// Also consolidate shared [lints] tables into [workspace.lints]
+    pub process_lints: bool,
     pub output_format: OutputFormat,
     pub output_callback: Option<OutputCallback>,
 }
 
+impl Default for Config {
+    /// The same settings as the command line without flags
+    fn default() -> Self {
+        Config {
+            fix: false,
+            process_dependencies: true,
+            process_dev_dependencies: true,
+            process_build_dependencies: true,
+            workspace_path: None,
+            exclude: Vec::new(),
+            min_members: 2,
+            exclude_members: Vec::new(),
+            check: false,
+            version_resolution_strategy: VersionResolutionStrategy::HighestCompatible,
+            version_resolution_overrides: Vec::new(),
+            version_resolver: None,
+            prerelease_policy: PrereleasePolicy::Allow,
+            exact_pin_policy: ExactPinPolicy::Conflict,
+            respect_rust_version: false,
+            registry_index: None,
+            deny_breaking_changes: false,
+            partial_consolidation: false,
+            default_features_strategy: DefaultFeaturesStrategy::Skip,
+            version_format: VersionFormat::AsWritten,
+            consolidate_path_dependencies: false,
+            hoist_features: false,
+            prune_unused: false,
+            demote: false,
+            process_package: false,
+            process_lints: false,
+            output_format: OutputFormat::Text,
+            output_callback: None,
+        }
+    }
+}
+
+impl Config {
+    /// Apply every setting `project` sets, e.g. from [`load_project_config`], keeping
+    /// the current value of the others. Its per-dependency overrides are added after
+    /// the configured ones, which keep precedence
+    pub fn with_project_config(mut self, project: ProjectConfig) -> Self {
+        macro_rules! apply {
+            ($($field:ident),* $(,)?) => {
+                $(if let Some(value) = project.$field {
+                    self.$field = value;
+                })*
+            };
+        }
+        apply!(
+            process_dependencies,
+            process_dev_dependencies,
+            process_build_dependencies,
+            exclude,
+            exclude_members,
+            min_members,
+            version_resolution_strategy,
+            prerelease_policy,
+            exact_pin_policy,
+            respect_rust_version,
+            deny_breaking_changes,
+            partial_consolidation,
+            default_features_strategy,
+            version_format,
+            consolidate_path_dependencies,
+            hoist_features,
+            prune_unused,
+            demote,
+            process_package,
+            process_lints,
+        );
+        if project.registry_index.is_some() {
+            self.registry_index = project.registry_index;
+        }
+        self.version_resolution_overrides
+            .extend(project.version_resolution_overrides);
+        self
+    }
+}
+
+/// Configuration for `inline`, which undoes consolidation
+pub struct InlineConfig {
+    pub workspace_path: Option<std::path::PathBuf>,
+    /// [workspace.dependencies] entries to inline, by name or glob pattern
+    pub dependencies: Vec<glob::Pattern>,
+    pub output_callback: Option<OutputCallback>,
+}
+
 macro_rules! write_output {
     ($config:expr, $($arg:tt)*) => {{
         let text = format!($($arg)*);
@@ -60,11 +291,57 @@ macro_rules! write_output {
     }};
 }
 
+/// Describe what will be (or was) consolidated, e.g. "3 dependencies and 2 package fields"
+fn describe_consolidation(
+    analysis: &dependency::DependencyAnalysis,
+    package_analysis: &PackageAnalysis,
+    lint_analysis: &LintAnalysis,
+) -> String {
+    let mut parts = vec![format!("{} dependencies", analysis.common_deps.len())];
+    if !package_analysis.common_fields.is_empty() {
+        parts.push(format!(
+            "{} package fields",
+            package_analysis.common_fields.len()
+        ));
+    }
+    if lint_analysis.common_lints.is_some() {
+        parts.push("1 lint table".to_string());
+    }
+
+    match parts.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        _ => parts.join(""),
+    }
+}
+
+/// Read defaults from `[workspace.metadata.workspace-deps]` in the root manifest
+/// and from `workspace-deps.toml`, the latter taking precedence
+pub fn load_project_config(workspace_path: Option<&std::path::Path>) -> Result<ProjectConfig> {
+    let workspace = discover_workspace(workspace_path)?;
+    ProjectConfig::load(workspace.root_dir())
+}
+
+/// Print the effective settings, in the format `workspace-deps.toml` accepts
+pub fn print_config(config: &Config) -> Result<()> {
+    write_output!(config, "{}", config_file::render(config));
+    Ok(())
+}
+
 /// Main entry point
 pub fn run(config: Config) -> Result<()> {
     let mut workspace = discover_workspace(config.workspace_path.as_deref())?;
     let num_filtered_patterns = workspace.filter_members_by_patterns(&config.exclude_members);
 
+    // Members left out by pattern (or by their own metadata, see below) may still
+    // inherit entries that look unused or rarely used
+    let mut excluded_refs = std::collections::HashSet::new();
+    if config.prune_unused || config.demote {
+        for member in &workspace.excluded_members {
+            let parsed = parse_dependencies(&member.manifest_path, ALL_KINDS)?;
+            excluded_refs.extend(parsed.workspace_refs.into_iter().map(|(name, _)| name));
+        }
+    }
+
     if config.output_format == OutputFormat::Text {
         if num_filtered_patterns > 0 {
             write_output!(
@@ -78,49 +355,141 @@ pub fn run(config: Config) -> Result<()> {
         }
     }
 
-    let sections: Vec<_> = [
-        (config.process_dependencies, DepSection::Dependencies),
-        (config.process_dev_dependencies, DepSection::DevDependencies),
+    let kinds: Vec<_> = [
+        (config.process_dependencies, DepKind::Dependencies),
+        (config.process_dev_dependencies, DepKind::DevDependencies),
         (
             config.process_build_dependencies,
-            DepSection::BuildDependencies,
+            DepKind::BuildDependencies,
         ),
     ]
     .iter()
-    .filter_map(|(enabled, section)| enabled.then_some(*section))
+    .filter_map(|(enabled, kind)| enabled.then_some(*kind))
     .collect();
 
-    if sections.is_empty() {
+    if kinds.is_empty()
+        && !config.process_package
+        && !config.process_lints
+        && !config.prune_unused
+        && !config.demote
+    {
         if config.output_format == OutputFormat::Text {
             write_output!(&config, "No dependency sections selected for processing.\n");
         }
         return Ok(());
     }
 
-    let workspace_data = parse_workspace_data(&workspace, &sections)?;
-    let analysis = analyze_workspace(
+    let workspace_data =
+        parse_workspace_data(&workspace, &kinds, config.consolidate_path_dependencies)?;
+    excluded_refs.extend(workspace_data.opted_out_refs.iter().cloned());
+
+    let uses_lockfile = std::iter::once(&config.version_resolution_strategy)
+        .chain(
+            config
+                .version_resolution_overrides
+                .iter()
+                .map(|o| &o.strategy),
+        )
+        .any(|strategy| *strategy == VersionResolutionStrategy::Lockfile);
+    let lockfile = if uses_lockfile {
+        Some(Lockfile::load(&workspace.root_dir().join("Cargo.lock"))?)
+    } else {
+        None
+    };
+    let uses_registry_index = config.respect_rust_version
+        || std::iter::once(&config.version_resolution_strategy)
+            .chain(
+                config
+                    .version_resolution_overrides
+                    .iter()
+                    .map(|o| &o.strategy),
+            )
+            .any(|strategy| *strategy == VersionResolutionStrategy::LatestCompatible);
+    let registry_index = match &config.registry_index {
+        _ if !uses_registry_index => None,
+        Some(dir) => Some(RegistryIndex::open(dir)?),
+        None => Some(RegistryIndex::from_cargo_home()?),
+    };
+    let rust_versions = collect_rust_versions(&workspace);
+    let mut analysis = analyze_workspace(
         &workspace_data,
         &config.exclude,
         config.min_members,
-        &config.version_resolution_strategy,
+        config.hoist_features,
+        &ResolutionOptions {
+            version_resolution_strategy: &config.version_resolution_strategy,
+            version_resolver: config.version_resolver.as_deref(),
+            version_resolution_overrides: &config.version_resolution_overrides,
+            prerelease_policy: &config.prerelease_policy,
+            exact_pin_policy: &config.exact_pin_policy,
+            default_features_strategy: &config.default_features_strategy,
+            lockfile: lockfile.as_ref(),
+            registry_index: registry_index.as_ref(),
+            respect_rust_version: config.respect_rust_version,
+            partial_consolidation: config.partial_consolidation,
+            rust_versions: &rust_versions,
+        },
     )?;
+    for dep in &mut analysis.common_deps {
+        if let Some(version) = &dep.version {
+            dep.version = Some(version_resolver::format_requirement(
+                version,
+                &config.version_format,
+            ));
+        }
+        // Classified after formatting, since that is the requirement members end up with
+        dep.classify_member_changes();
+    }
+    if config.demote {
+        demote_rarely_used(&mut analysis, &workspace_data, config.min_members, |name| {
+            !excluded_refs.contains(name) && !config.exclude.iter().any(|e| e == name)
+        });
+    }
+    let unused_to_prune = if config.prune_unused {
+        let mut unused: Vec<String> = analysis
+            .unused_workspace_deps
+            .iter()
+            .filter(|name| !excluded_refs.contains(*name) && !config.exclude.contains(name))
+            .cloned()
+            .collect();
+        unused.sort();
+        unused
+    } else {
+        Vec::new()
+    };
+    let skipped_members = workspace_data.skipped_members();
+    let package_analysis = if config.process_package {
+        analyze_package_fields(&workspace, &skipped_members, config.min_members)?
+    } else {
+        PackageAnalysis::default()
+    };
+    let lint_analysis = if config.process_lints {
+        analyze_lints(&workspace, &skipped_members, config.min_members)?
+    } else {
+        LintAnalysis::default()
+    };
 
     let workspace_root = workspace
         .root_manifest
         .parent()
         .and_then(|p| p.to_str())
         .unwrap_or(".");
-    let mut output_data =
-        output_format::Output::new(&analysis, workspace_root, workspace.members.len());
+    let mut output_data = output_format::Output::new(
+        &analysis,
+        &package_analysis,
+        &lint_analysis,
+        workspace_root,
+        workspace.members.len(),
+    );
     output_data.sort();
 
     // Output text mode
     if config.output_format == OutputFormat::Text {
-        write_output!(
-            &config,
-            "{}",
-            output_data.to_text(&config.version_resolution_strategy)
-        );
+        let resolver_name = match &config.version_resolver {
+            Some(resolver) => resolver.name(),
+            None => format!("{:?}", config.version_resolution_strategy),
+        };
+        write_output!(&config, "{}", output_data.to_text(&resolver_name));
     }
 
     // Check mode: return error if there are dependencies to consolidate
@@ -130,24 +499,49 @@ pub fn run(config: Config) -> Result<()> {
             write_output!(&config, "{}", output_data.to_json()?);
         }
 
-        if !analysis.common_deps.is_empty() {
+        let consolidation_count = analysis.common_deps.len()
+            + package_analysis.common_fields.len()
+            + usize::from(lint_analysis.common_lints.is_some());
+        let conflict_count = analysis.conflicts.len()
+            + package_analysis.conflicts.len()
+            + lint_analysis.conflicts.len();
+
+        if consolidation_count > 0 {
             if config.output_format == OutputFormat::Text {
                 write_output!(
                     &config,
-                    "Check failed: {} dependencies could be consolidated\n",
-                    analysis.common_deps.len()
+                    "Check failed: {} could be consolidated\n",
+                    describe_consolidation(&analysis, &package_analysis, &lint_analysis)
                 );
             }
-            return Err(error::CheckFailure::Consolidation(analysis.common_deps.len()).into());
-        } else if !analysis.conflicts.is_empty() {
+            return Err(error::CheckFailure::Consolidation(consolidation_count).into());
+        } else if conflict_count > 0 {
             if config.output_format == OutputFormat::Text {
                 write_output!(
                     &config,
                     "Check failed: {} unresolved conflicts\n",
-                    analysis.conflicts.len()
+                    conflict_count
                 );
             }
-            return Err(error::CheckFailure::Conflicts(analysis.conflicts.len()).into());
+            return Err(error::CheckFailure::Conflicts(conflict_count).into());
+        } else if !unused_to_prune.is_empty() {
+            if config.output_format == OutputFormat::Text {
+                write_output!(
+                    &config,
+                    "Check failed: {} unused workspace dependencies\n",
+                    unused_to_prune.len()
+                );
+            }
+            return Err(error::CheckFailure::UnusedDependencies(unused_to_prune.len()).into());
+        } else if !analysis.demoted_deps.is_empty() {
+            if config.output_format == OutputFormat::Text {
+                write_output!(
+                    &config,
+                    "Check failed: {} workspace dependencies could be demoted\n",
+                    analysis.demoted_deps.len()
+                );
+            }
+            return Err(error::CheckFailure::Demotion(analysis.demoted_deps.len()).into());
         } else {
             if config.output_format == OutputFormat::Text {
                 write_output!(&config, "Check passed: no dependencies to consolidate\n");
@@ -156,7 +550,12 @@ pub fn run(config: Config) -> Result<()> {
         }
     }
 
-    if analysis.common_deps.is_empty() {
+    if analysis.common_deps.is_empty()
+        && package_analysis.common_fields.is_empty()
+        && lint_analysis.common_lints.is_none()
+        && unused_to_prune.is_empty()
+        && analysis.demoted_deps.is_empty()
+    {
         // Output json mode
         if config.output_format == OutputFormat::Json {
             write_output!(&config, "{}", output_data.to_json()?);
@@ -164,6 +563,25 @@ pub fn run(config: Config) -> Result<()> {
         return Ok(());
     }
 
+    if config.deny_breaking_changes {
+        let breaking: Vec<&str> = analysis
+            .common_deps
+            .iter()
+            .filter(|dep| {
+                dep.member_changes
+                    .values()
+                    .any(|change| *change == version_resolver::VersionChange::Breaking)
+            })
+            .map(|dep| dep.name.as_str())
+            .collect();
+        if !breaking.is_empty() {
+            anyhow::bail!(
+                "Refusing to apply breaking version changes to: {}",
+                breaking.join(", ")
+            );
+        }
+    }
+
     // Prompt for confirmation unless --fix is used
     if !config.fix {
         write_output!(&config, "Apply these changes? [y/N] ");
@@ -189,6 +607,13 @@ pub fn run(config: Config) -> Result<()> {
     std::fs::write(&workspace.root_manifest, &workspace_content)
         .with_context(|| format!("Failed to write {}", workspace.root_manifest.display()))?;
 
+    if !unused_to_prune.is_empty() {
+        let workspace_content =
+            remove_workspace_dependencies(&workspace.root_manifest, &unused_to_prune)?;
+        std::fs::write(&workspace.root_manifest, &workspace_content)
+            .with_context(|| format!("Failed to write {}", workspace.root_manifest.display()))?;
+    }
+
     for member in &workspace.members {
         let member_content =
             update_member_dependencies(&member.manifest_path, &analysis.common_deps, &member.name)?;
@@ -201,16 +626,162 @@ pub fn run(config: Config) -> Result<()> {
         }
     }
 
+    if !analysis.demoted_deps.is_empty() {
+        let patterns = analysis
+            .demoted_deps
+            .iter()
+            .map(|dep| glob::Pattern::new(&glob::Pattern::escape(&dep.name)))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let entries = workspace_dependency_entries(&workspace.root_manifest, &patterns)?;
+        inline_into_members(&workspace, &entries)?;
+
+        let names: Vec<String> = entries.into_iter().map(|entry| entry.name).collect();
+        let workspace_content = remove_workspace_dependencies(&workspace.root_manifest, &names)?;
+        std::fs::write(&workspace.root_manifest, &workspace_content)
+            .with_context(|| format!("Failed to write {}", workspace.root_manifest.display()))?;
+    }
+
+    if !package_analysis.common_fields.is_empty() {
+        let workspace_content =
+            update_workspace_package(&workspace.root_manifest, &package_analysis.common_fields)?;
+        std::fs::write(&workspace.root_manifest, &workspace_content)
+            .with_context(|| format!("Failed to write {}", workspace.root_manifest.display()))?;
+
+        for member in &workspace.members {
+            let member_content = update_member_package(
+                &member.manifest_path,
+                &package_analysis.common_fields,
+                &member.name,
+            )?;
+
+            // only write if there are changes
+            let original = std::fs::read_to_string(&member.manifest_path)?;
+            if original != member_content {
+                std::fs::write(&member.manifest_path, &member_content).with_context(|| {
+                    format!("Failed to write {}", member.manifest_path.display())
+                })?;
+            }
+        }
+    }
+
+    if let Some(common_lints) = &lint_analysis.common_lints {
+        let workspace_content = update_workspace_lints(&workspace.root_manifest, common_lints)?;
+        std::fs::write(&workspace.root_manifest, &workspace_content)
+            .with_context(|| format!("Failed to write {}", workspace.root_manifest.display()))?;
+
+        for member in &workspace.members {
+            let member_content =
+                update_member_lints(&member.manifest_path, common_lints, &member.name)?;
+
+            // only write if there are changes
+            let original = std::fs::read_to_string(&member.manifest_path)?;
+            if original != member_content {
+                std::fs::write(&member.manifest_path, &member_content).with_context(|| {
+                    format!("Failed to write {}", member.manifest_path.display())
+                })?;
+            }
+        }
+    }
+
     // Output final summary
     if config.output_format == OutputFormat::Text {
+        let consolidated = !analysis.common_deps.is_empty()
+            || !package_analysis.common_fields.is_empty()
+            || lint_analysis.common_lints.is_some();
+        if consolidated {
+            write_output!(
+                &config,
+                "Consolidated {}\n",
+                describe_consolidation(&analysis, &package_analysis, &lint_analysis)
+            );
+        }
+        if !unused_to_prune.is_empty() {
+            write_output!(
+                &config,
+                "Removed {} unused workspace dependencies\n",
+                unused_to_prune.len()
+            );
+        }
+        if !analysis.demoted_deps.is_empty() {
+            write_output!(
+                &config,
+                "Demoted {} workspace dependencies\n",
+                analysis.demoted_deps.len()
+            );
+        }
+    } else {
+        write_output!(&config, "{}", output_data.to_json()?);
+    }
+
+    Ok(())
+}
+
+/// Copy [workspace.dependencies] entries back into the members inheriting them,
+/// then remove the entries no member references anymore
+pub fn inline(config: InlineConfig) -> Result<()> {
+    let workspace = discover_workspace(config.workspace_path.as_deref())?;
+    write_output!(&config, "Found {} members\n", workspace.members.len());
+
+    let entries = workspace_dependency_entries(&workspace.root_manifest, &config.dependencies)?;
+
+    let mut inlined = inline_into_members(&workspace, &entries)?;
+
+    let mut still_referenced = std::collections::HashSet::new();
+    for member in &workspace.members {
+        let parsed = parse_dependencies(&member.manifest_path, ALL_KINDS)?;
+        still_referenced.extend(parsed.workspace_refs.into_iter().map(|(name, _)| name));
+    }
+    let mut removed: Vec<String> = entries
+        .iter()
+        .map(|entry| entry.name.clone())
+        .filter(|name| !still_referenced.contains(name))
+        .collect();
+    removed.sort();
+
+    let workspace_content = remove_workspace_dependencies(&workspace.root_manifest, &removed)?;
+    std::fs::write(&workspace.root_manifest, &workspace_content)
+        .with_context(|| format!("Failed to write {}", workspace.root_manifest.display()))?;
+
+    if inlined.is_empty() {
+        write_output!(&config, "\nNo members inherit the selected dependencies.\n");
+    } else {
+        write_output!(&config, "\nInlined:\n");
+        for (name, members) in &mut inlined {
+            members.sort();
+            write_output!(&config, "  {} in: {}\n", name, members.join(", "));
+        }
+    }
+    if !removed.is_empty() {
         write_output!(
             &config,
-            "Consolidated {} dependencies\n",
-            analysis.common_deps.len()
+            "\nRemoved from [workspace.dependencies]: {}\n",
+            removed.join(", ")
         );
-    } else {
-        write_output!(&config, "{}", output_data.to_json()?);
     }
 
     Ok(())
 }
+
+/// Replace `workspace = true` in every member inheriting one of the entries,
+/// returning dependency -> members it was inlined into
+fn inline_into_members(
+    workspace: &WorkspaceInfo,
+    entries: &[InlinedDependency],
+) -> Result<std::collections::BTreeMap<String, Vec<String>>> {
+    let mut inlined: std::collections::BTreeMap<String, Vec<String>> = Default::default();
+    for member in &workspace.members {
+        let (member_content, names) =
+            inline_member_dependencies(&member.manifest_path, entries, |path| {
+                workspace.path_for_member(&member.manifest_path, path)
+            })?;
+        if names.is_empty() {
+            continue;
+        }
+        std::fs::write(&member.manifest_path, &member_content)
+            .with_context(|| format!("Failed to write {}", member.manifest_path.display()))?;
+        for name in names {
+            inlined.entry(name).or_default().push(member.name.clone());
+        }
+    }
+    Ok(inlined)
+}
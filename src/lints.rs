@@ -0,0 +1,252 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+use toml_edit::{Item, TableLike, Value};
+
+use crate::manifest::read_manifest;
+use crate::workspace::WorkspaceInfo;
+
+/// Lint levels keyed by (tool, lint), e.g. ("clippy", "unwrap_used") -> "deny"
+pub(crate) type LintTable = BTreeMap<(String, String), String>;
+
+/// Lint values as written, grouped by tool, e.g. "clippy" -> [("unwrap_used", "deny")]
+pub(crate) type WrittenLints = BTreeMap<String, Vec<(String, Value)>>;
+
+#[derive(Debug, Default)]
+pub(crate) struct LintAnalysis {
+    /// Lint table that will be (or already is) defined in [workspace.lints]
+    pub(crate) common_lints: Option<CommonLints>,
+
+    /// Members whose lint tables differ from the agreed table
+    pub(crate) conflicts: Vec<LintConflict>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct CommonLints {
+    /// Agreed lint levels, as written by the first member using them
+    pub(crate) lints: WrittenLints,
+    /// Normalized lint levels for display
+    pub(crate) levels: LintTable,
+    /// Members that will use `lints.workspace = true`
+    pub(crate) members: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct LintConflict {
+    pub(crate) member: String,
+    /// Lints that differ from the agreed table, e.g. "clippy::unwrap_used = \"warn\" (expected \"deny\")"
+    pub(crate) differences: Vec<String>,
+}
+
+/// Normalize a lint level so that equivalent spellings compare equal
+/// Examples: `"warn"` and `{ level = "warn" }` both become `"warn"`,
+/// `{ priority = -1, level = "warn" }` becomes `{ level = "warn", priority = -1 }`
+fn normalize_level(item: &Item) -> Option<String> {
+    if let Some(level) = item.as_str() {
+        return Some(format!("\"{}\"", level));
+    }
+
+    let table = item.as_table_like()?;
+    let mut fields: BTreeMap<&str, String> = BTreeMap::new();
+    for (key, value) in table.iter() {
+        let mut value = value.as_value()?.clone();
+        value.decor_mut().clear();
+        fields.insert(key, value.to_string());
+    }
+
+    match fields.len() {
+        1 => fields.get("level").cloned(),
+        _ => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(key, value)| format!("{} = {}", key, value))
+                .collect();
+            Some(format!("{{ {} }}", fields.join(", ")))
+        }
+    }
+}
+
+/// Flatten a `[lints]` table into normalized levels and the values as written
+fn parse_lint_table(lints: &dyn TableLike) -> (LintTable, WrittenLints) {
+    let mut levels = LintTable::new();
+    let mut written: WrittenLints = BTreeMap::new();
+
+    for (tool, tool_item) in lints.iter() {
+        let Some(tool_table) = tool_item.as_table_like() else {
+            continue;
+        };
+        for (lint, item) in tool_table.iter() {
+            let Some(level) = normalize_level(item) else {
+                continue;
+            };
+            let value = match item {
+                Item::Value(value) => value.clone(),
+                other => match other.as_table_like() {
+                    Some(table) => {
+                        let mut inline = toml_edit::InlineTable::new();
+                        for (key, value) in table.iter() {
+                            if let Some(value) = value.as_value() {
+                                inline.insert(key, value.clone());
+                            }
+                        }
+                        Value::InlineTable(inline)
+                    }
+                    None => continue,
+                },
+            };
+            levels.insert((tool.to_string(), lint.to_string()), level);
+            written
+                .entry(tool.to_string())
+                .or_default()
+                .push((lint.to_string(), value));
+        }
+    }
+
+    (levels, written)
+}
+
+/// Describe how a member's lint table differs from the agreed one
+fn describe_differences(member: &LintTable, agreed: &LintTable) -> Vec<String> {
+    let mut differences = Vec::new();
+
+    for ((tool, lint), level) in member {
+        match agreed.get(&(tool.clone(), lint.clone())) {
+            Some(expected) if expected == level => {}
+            Some(expected) => differences.push(format!(
+                "{}::{} = {} (expected {})",
+                tool, lint, level, expected
+            )),
+            None => differences.push(format!("{}::{} = {} (extra)", tool, lint, level)),
+        }
+    }
+    for ((tool, lint), level) in agreed {
+        if !member.contains_key(&(tool.clone(), lint.clone())) {
+            differences.push(format!("{}::{} = {} (missing)", tool, lint, level));
+        }
+    }
+
+    differences
+}
+
+/// Lints at the level more than half of the members' tables use, shared by at least
+/// `min_members` members, as written by the first of them
+fn agreed_levels(
+    member_tables: &[(String, LintTable, WrittenLints)],
+    min_members: usize,
+) -> (LintTable, WrittenLints) {
+    let mut votes: BTreeMap<(&(String, String), &String), usize> = BTreeMap::new();
+    for (_, table, _) in member_tables {
+        for (lint, level) in table {
+            *votes.entry((lint, level)).or_default() += 1;
+        }
+    }
+
+    let levels: LintTable = votes
+        .into_iter()
+        .filter(|(_, count)| *count * 2 > member_tables.len() && *count >= min_members)
+        .map(|((lint, level), _)| (lint.clone(), level.clone()))
+        .collect();
+
+    let mut written: WrittenLints = BTreeMap::new();
+    for (_, table, member_written) in member_tables {
+        for (tool, lints) in member_written {
+            for (lint, value) in lints {
+                let key = (tool.clone(), lint.clone());
+                let agreed = levels
+                    .get(&key)
+                    .is_some_and(|level| table.get(&key) == Some(level));
+                let entries = written.entry(tool.clone()).or_default();
+                if agreed && !entries.iter().any(|(name, _)| name == lint) {
+                    entries.push((lint.clone(), value.clone()));
+                }
+            }
+        }
+    }
+    written.retain(|_, lints| !lints.is_empty());
+
+    (levels, written)
+}
+
+/// Find the `[lints]` table shared by members that can move to [workspace.lints],
+/// leaving out `skipped_members`
+pub(crate) fn analyze_lints(
+    workspace: &WorkspaceInfo,
+    skipped_members: &HashSet<&str>,
+    min_members: usize,
+) -> Result<LintAnalysis> {
+    let root = read_manifest(&workspace.root_manifest)?;
+    let workspace_lints = root
+        .get("workspace")
+        .and_then(|ws| ws.get("lints"))
+        .and_then(|lints| lints.as_table_like())
+        .map(parse_lint_table);
+    let workspace_lints_defined = workspace_lints.is_some();
+
+    let mut member_tables: Vec<(String, LintTable, WrittenLints)> = Vec::new();
+
+    for member in &workspace.members {
+        if skipped_members.contains(member.name.as_str()) {
+            continue;
+        }
+        let doc = read_manifest(&member.manifest_path)?;
+        let Some(lints) = doc.get("lints").and_then(|l| l.as_table_like()) else {
+            continue;
+        };
+        if lints.contains_key("workspace") {
+            continue;
+        }
+
+        let (levels, written) = parse_lint_table(lints);
+        member_tables.push((member.name.clone(), levels, written));
+    }
+
+    let mut analysis = LintAnalysis::default();
+    if member_tables.is_empty() {
+        return Ok(analysis);
+    }
+
+    // An existing [workspace.lints] wins, otherwise the levels most members agree on
+    let (levels, lints) = match workspace_lints {
+        Some(agreed) => agreed,
+        None => agreed_levels(&member_tables, min_members),
+    };
+
+    // Cargo does not allow `lints.workspace = true` next to other lints, so only
+    // members with exactly the agreed table inherit it
+    let (members, differing): (Vec<_>, Vec<_>) = member_tables
+        .iter()
+        .partition(|(_, table, _)| *table == levels);
+    let members: Vec<String> = members
+        .into_iter()
+        .map(|(name, _, _)| name.clone())
+        .collect();
+
+    // Identical tables nobody agrees on are left alone
+    if !levels.is_empty()
+        || member_tables
+            .iter()
+            .any(|(_, table, _)| *table != member_tables[0].1)
+    {
+        analysis.conflicts = differing
+            .into_iter()
+            .map(|(member, table, _)| LintConflict {
+                member: member.clone(),
+                differences: describe_differences(table, &levels),
+            })
+            .collect();
+    }
+
+    let enough = if workspace_lints_defined {
+        !members.is_empty()
+    } else {
+        !levels.is_empty() && members.len() >= min_members
+    };
+    if enough {
+        analysis.common_lints = Some(CommonLints {
+            lints,
+            levels,
+            members,
+        });
+    }
+
+    Ok(analysis)
+}
@@ -0,0 +1,63 @@
+use anyhow::{Context, Result};
+use semver::Version;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use toml_edit::DocumentMut;
+
+/// Registry package versions recorded in a `Cargo.lock`
+#[derive(Debug, Default)]
+pub(crate) struct Lockfile {
+    /// Maps package name -> locked versions
+    versions: HashMap<String, Vec<Version>>,
+}
+
+impl Lockfile {
+    /// Read the `Cargo.lock` at the given path
+    pub(crate) fn load(lockfile_path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(lockfile_path)
+            .with_context(|| format!("Failed to read {}", lockfile_path.display()))?;
+
+        let doc = content
+            .parse::<DocumentMut>()
+            .with_context(|| format!("Failed to parse TOML at {}", lockfile_path.display()))?;
+
+        let mut versions: HashMap<String, Vec<Version>> = HashMap::new();
+
+        let packages = doc
+            .get("package")
+            .and_then(|p| p.as_array_of_tables())
+            .into_iter()
+            .flatten();
+
+        for package in packages {
+            // Workspace members and path dependencies have no source
+            let from_registry =
+                package
+                    .get("source")
+                    .and_then(|s| s.as_str())
+                    .is_some_and(|source| {
+                        source.starts_with("registry+") || source.starts_with("sparse+")
+                    });
+            if !from_registry {
+                continue;
+            }
+
+            let name = package.get("name").and_then(|n| n.as_str());
+            let version = package
+                .get("version")
+                .and_then(|v| v.as_str())
+                .and_then(|v| Version::parse(v).ok());
+            if let (Some(name), Some(version)) = (name, version) {
+                versions.entry(name.to_string()).or_default().push(version);
+            }
+        }
+
+        Ok(Lockfile { versions })
+    }
+
+    /// Locked versions of a package, empty if it is not in the lockfile
+    pub(crate) fn versions(&self, package: &str) -> &[Version] {
+        self.versions.get(package).map_or(&[], |v| v.as_slice())
+    }
+}
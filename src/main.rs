@@ -1,5 +1,9 @@
 use anyhow::Result;
-use cargo_workspace_deps::{CheckFailure, Config, OutputFormat, VersionResolutionStrategy, run};
+use cargo_workspace_deps::{
+    CheckFailure, Config, DefaultFeaturesStrategy, ExactPinPolicy, InlineConfig, OutputFormat,
+    PrereleasePolicy, ProjectConfig, VersionFormat, VersionResolutionOverride,
+    VersionResolutionStrategy, inline, load_project_config, print_config, run,
+};
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -22,7 +26,11 @@ enum Cargo {
     long_about = "Moves shared dependencies to [workspace.dependencies] and updates members to use workspace = true.
 Reduces duplication and ensures version consistency across the workspace."
 )]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Apply changes without prompting for confirmation
     #[arg(long)]
     fix: bool,
@@ -36,17 +44,29 @@ struct Args {
     manifest_path: Option<std::path::PathBuf>,
 
     /// Skip processing [dependencies] section
-    #[arg(long)]
+    #[arg(long, overrides_with = "dependencies")]
     no_dependencies: bool,
 
+    /// Process [dependencies] even when the settings skip it
+    #[arg(long, overrides_with = "no_dependencies")]
+    dependencies: bool,
+
     /// Skip processing [dev-dependencies] section
-    #[arg(long)]
+    #[arg(long, overrides_with = "dev_dependencies")]
     no_dev_dependencies: bool,
 
+    /// Process [dev-dependencies] even when the settings skip it
+    #[arg(long, overrides_with = "no_dev_dependencies")]
+    dev_dependencies: bool,
+
     /// Skip processing [build-dependencies] section
-    #[arg(long)]
+    #[arg(long, overrides_with = "build_dependencies")]
     no_build_dependencies: bool,
 
+    /// Process [build-dependencies] even when the settings skip it
+    #[arg(long, overrides_with = "no_build_dependencies")]
+    build_dependencies: bool,
+
     /// Skip specific dependencies by name (comma-separated, e.g. serde,tokio)
     #[arg(long, value_delimiter = ',')]
     exclude: Vec<String>,
@@ -55,17 +75,154 @@ struct Args {
     #[arg(long, value_delimiter = ',', value_parser = parse_glob_pattern)]
     exclude_members: Vec<glob::Pattern>,
 
-    /// Only consolidate dependencies appearing in at least N members
-    #[arg(long, default_value = "2")]
-    min_members: usize,
+    /// Only consolidate dependencies appearing in at least N members [default: 2]
+    #[arg(long, value_name = "MIN_MEMBERS")]
+    min_members: Option<usize>,
+
+    /// Strategy for resolving version conflicts [default: highest-compatible]
+    #[arg(long, value_enum)]
+    version_resolution: Option<VersionResolutionStrategy>,
+
+    /// Strategy for specific dependencies by name or glob, taking precedence over
+    /// workspace-deps.toml (comma-separated, e.g. rustls=fail,openssl*=fail)
+    #[arg(long, value_delimiter = ',', value_name = "PATTERN=STRATEGY")]
+    version_resolution_override: Vec<VersionResolutionOverride>,
+
+    /// How prerelease versions (e.g. 1.0.0-alpha) are treated during resolution [default: allow]
+    #[arg(long, value_enum)]
+    prerelease: Option<PrereleasePolicy>,
+
+    /// How exact pins (=x.y.z) are treated when members disagree on a version [default: conflict]
+    #[arg(long, value_enum)]
+    exact_pins: Option<ExactPinPolicy>,
+
+    /// Skip versions needing a newer Rust than the workspace or member rust-version,
+    /// read from the local registry index cache (no network access)
+    #[arg(long, overrides_with = "no_msrv")]
+    msrv: bool,
+
+    /// Turn off --msrv when the settings enable it
+    #[arg(long, overrides_with = "msrv")]
+    no_msrv: bool,
+
+    /// Local registry directory to read crate versions from (for --msrv and latest-compatible),
+    /// instead of the index cache under $CARGO_HOME
+    #[arg(long, value_name = "DIR")]
+    registry_index: Option<std::path::PathBuf>,
+
+    /// Refuse to apply changes that move a member to a SemVer-incompatible version
+    #[arg(long, overrides_with = "no_deny_breaking")]
+    deny_breaking: bool,
+
+    /// Turn off --deny-breaking when the settings enable it
+    #[arg(long, overrides_with = "deny_breaking")]
+    no_deny_breaking: bool,
 
-    /// Strategy for resolving version conflicts
-    #[arg(long, value_enum, default_value = "highest-compatible")]
-    version_resolution: VersionResolutionStrategy,
+    /// When versions conflict, consolidate the largest group of SemVer-compatible members
+    /// and leave the others on their explicit versions
+    #[arg(long, overrides_with = "no_partial")]
+    partial: bool,
+
+    /// Turn off --partial when the settings enable it
+    #[arg(long, overrides_with = "partial")]
+    no_partial: bool,
+
+    /// Strategy for resolving default-features conflicts [default: skip]
+    #[arg(long, value_enum)]
+    default_features_resolution: Option<DefaultFeaturesStrategy>,
+
+    /// How versions are written to [workspace.dependencies] [default: as-written]
+    #[arg(long, value_enum)]
+    version_format: Option<VersionFormat>,
+
+    /// Hoist path dependencies on other workspace members into [workspace.dependencies]
+    #[arg(long, overrides_with = "no_path_dependencies")]
+    path_dependencies: bool,
+
+    /// Turn off --path-dependencies when the settings enable it
+    #[arg(long, overrides_with = "path_dependencies")]
+    no_path_dependencies: bool,
+
+    /// Move features requested by every member into the [workspace.dependencies] entry
+    #[arg(long, overrides_with = "no_hoist_features")]
+    hoist_features: bool,
+
+    /// Turn off --hoist-features when the settings enable it
+    #[arg(long, overrides_with = "hoist_features")]
+    no_hoist_features: bool,
+
+    /// Remove [workspace.dependencies] entries no member uses, along with their comments
+    /// (with --check, fail if there are any)
+    #[arg(long, overrides_with = "no_prune_unused")]
+    prune_unused: bool,
+
+    /// Turn off --prune-unused when the settings enable it
+    #[arg(long, overrides_with = "prune_unused")]
+    no_prune_unused: bool,
+
+    /// Move [workspace.dependencies] entries used by fewer than --min-members members
+    /// back into those members as explicit specs
+    #[arg(long, overrides_with = "no_demote")]
+    demote: bool,
+
+    /// Turn off --demote when the settings enable it
+    #[arg(long, overrides_with = "demote")]
+    no_demote: bool,
+
+    /// Also consolidate shared [package] fields (edition, license, ...) into [workspace.package]
+    #[arg(long, overrides_with = "no_package")]
+    package: bool,
+
+    /// Turn off --package when the settings enable it
+    #[arg(long, overrides_with = "package")]
+    no_package: bool,
+
+    /// Also consolidate shared [lints] tables into [workspace.lints]
+    #[arg(long, overrides_with = "no_lints")]
+    lints: bool,
+
+    /// Turn off --lints when the settings enable it
+    #[arg(long, overrides_with = "lints")]
+    no_lints: bool,
 
     /// Output format
     #[arg(long, value_enum, default_value = "text")]
     format: OutputFormat,
+
+    /// Print the effective settings, after merging flags with [workspace.metadata.workspace-deps]
+    /// and workspace-deps.toml, and exit
+    #[arg(long)]
+    print_config: bool,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Move dependencies out of [workspace.dependencies] back into the members
+    ///
+    /// Replaces `workspace = true` with the version, package, registry and default-features
+    /// of the workspace entry, keeping member features and optional, then removes the
+    /// workspace entries nothing references anymore.
+    Inline(InlineArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct InlineArgs {
+    /// Dependencies to inline, by name or glob pattern (e.g. serde 'tokio*')
+    #[arg(required = true, value_parser = parse_glob_pattern)]
+    dependencies: Vec<glob::Pattern>,
+
+    /// Path to workspace directory (defaults to current directory)
+    #[arg(long, value_name = "PATH")]
+    manifest_path: Option<std::path::PathBuf>,
+}
+
+/// Setting given by a `--x`/`--no-x` pair, `None` when neither is given
+fn switch(on: bool, off: bool) -> Option<bool> {
+    match (on, off) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
 }
 
 fn parse_glob_pattern(s: &str) -> Result<glob::Pattern, String> {
@@ -75,25 +232,57 @@ fn parse_glob_pattern(s: &str) -> Result<glob::Pattern, String> {
 fn main() -> Result<()> {
     let Cargo::WorkspaceDeps(args) = Cargo::parse();
 
-    // JSON output for non-interactive paths only
-    if args.format == OutputFormat::Json && !args.fix && !args.check {
-        anyhow::bail!("JSON output requires --fix or --check flag (non-interactive mode)");
+    if let Some(Command::Inline(args)) = args.command {
+        return inline(InlineConfig {
+            workspace_path: args.manifest_path,
+            dependencies: args.dependencies,
+            output_callback: None,
+        });
     }
 
+    // Flags take precedence over workspace-deps.toml and the workspace metadata
+    let flags = ProjectConfig {
+        process_dependencies: switch(args.dependencies, args.no_dependencies),
+        process_dev_dependencies: switch(args.dev_dependencies, args.no_dev_dependencies),
+        process_build_dependencies: switch(args.build_dependencies, args.no_build_dependencies),
+        exclude: (!args.exclude.is_empty()).then_some(args.exclude),
+        exclude_members: (!args.exclude_members.is_empty()).then_some(args.exclude_members),
+        min_members: args.min_members,
+        version_resolution_strategy: args.version_resolution,
+        version_resolution_overrides: args.version_resolution_override,
+        prerelease_policy: args.prerelease,
+        exact_pin_policy: args.exact_pins,
+        respect_rust_version: switch(args.msrv, args.no_msrv),
+        registry_index: args.registry_index,
+        deny_breaking_changes: switch(args.deny_breaking, args.no_deny_breaking),
+        partial_consolidation: switch(args.partial, args.no_partial),
+        default_features_strategy: args.default_features_resolution,
+        version_format: args.version_format,
+        consolidate_path_dependencies: switch(args.path_dependencies, args.no_path_dependencies),
+        hoist_features: switch(args.hoist_features, args.no_hoist_features),
+        prune_unused: switch(args.prune_unused, args.no_prune_unused),
+        demote: switch(args.demote, args.no_demote),
+        process_package: switch(args.package, args.no_package),
+        process_lints: switch(args.lints, args.no_lints),
+    };
+    let project = load_project_config(args.manifest_path.as_deref())?;
     let config = Config {
         fix: args.fix,
-        process_dependencies: !args.no_dependencies,
-        process_dev_dependencies: !args.no_dev_dependencies,
-        process_build_dependencies: !args.no_build_dependencies,
-        workspace_path: args.manifest_path,
-        exclude: args.exclude,
-        min_members: args.min_members,
-        exclude_members: args.exclude_members,
         check: args.check,
-        version_resolution_strategy: args.version_resolution,
+        workspace_path: args.manifest_path,
         output_format: args.format,
-        output_callback: None,
-    };
+        ..Default::default()
+    }
+    .with_project_config(flags.or(project));
+
+    if args.print_config {
+        return print_config(&config);
+    }
+
+    // JSON output for non-interactive paths only
+    if args.format == OutputFormat::Json && !args.fix && !args.check {
+        anyhow::bail!("JSON output requires --fix or --check flag (non-interactive mode)");
+    }
 
     match run(config) {
         Ok(()) => Ok(()),
@@ -0,0 +1,17 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use toml_edit::DocumentMut;
+
+/// Stands in for the [workspace.*] table wherever members are listed
+pub(crate) const WORKSPACE_MARKER: &str = "workspace";
+
+/// Read and parse a Cargo.toml, keeping its formatting for edits
+pub(crate) fn read_manifest(manifest_path: &Path) -> Result<DocumentMut> {
+    let content = fs::read_to_string(manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+
+    content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse TOML at {}", manifest_path.display()))
+}
@@ -1,8 +1,11 @@
 use crate::VersionResolutionStrategy;
-use crate::dependency::{ConflictType, DependencyAnalysis};
+use crate::dependency::{ConflictType, DependencyAnalysis, GitReference, GitSource};
+use crate::lints::LintAnalysis;
+use crate::package::PackageAnalysis;
+use crate::version_resolver::VersionChange;
 use anyhow::{Context, Result};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Unified output structure that can be serialized to JSON or formatted as text
 #[derive(Debug, Clone, Serialize)]
@@ -12,6 +15,18 @@ pub(crate) struct Output {
     pub(crate) common_dependencies: Vec<Dependency>,
     pub(crate) conflicts: Vec<Conflict>,
     pub(crate) unused_workspace_dependencies: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) demoted_dependencies: Vec<DemotedDependency>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) skipped_by_members: Vec<SkippedDependencies>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) common_package_fields: Vec<PackageField>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) package_conflicts: Vec<PackageConflict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) common_lints: Option<Lints>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) lint_conflicts: Vec<LintConflict>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -31,7 +46,8 @@ pub(crate) struct Summary {
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct Dependency {
     pub(crate) name: String,
-    pub(crate) version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) version: Option<String>,
     pub(crate) members: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) package: Option<String>,
@@ -39,7 +55,35 @@ pub(crate) struct Dependency {
     pub(crate) registry: Option<String>,
     pub(crate) default_features: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) git: Option<GitSource>,
+    /// Features hoisted into the workspace entry
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) features: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) resolved_from: Option<HashMap<String, Vec<String>>>,
+    /// Per-dependency strategy used instead of the global one (text output only)
+    #[serde(skip)]
+    pub(crate) resolution_override: Option<VersionResolutionStrategy>,
+    /// Members whose requirement changes to the resolved version (text output only)
+    #[serde(skip)]
+    pub(crate) members_moved: usize,
+    /// Exact pins (`=x.y.z`) -> members using them
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub(crate) pins: HashMap<String, Vec<String>>,
+    /// Versions kept by members left out of the workspace entry -> members using them
+    #[serde(
+        rename = "diverging_members",
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub(crate) diverging: HashMap<String, Vec<String>>,
+    /// Member -> how its requirement changes, if this was resolved from a conflict
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) member_changes: BTreeMap<String, VersionChange>,
+    /// Maps "true"/"false" -> members if default-features was disabled to resolve a conflict
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) default_features_resolved_from: Option<HashMap<String, Vec<String>>>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -47,18 +91,67 @@ pub(crate) struct Conflict {
     pub(crate) name: String,
     pub(crate) version_specs: Vec<VersionSpec>,
     pub(crate) conflict_types: Vec<ConflictType>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) diagnostics: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct VersionSpec {
-    pub(crate) version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) version: Option<String>,
     pub(crate) default_features: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) git_reference: Option<GitReference>,
+    pub(crate) members: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct DemotedDependency {
+    pub(crate) name: String,
+    /// Members that get an explicit spec back
+    pub(crate) members: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct SkippedDependencies {
+    pub(crate) member: String,
+    /// The `[package.metadata.workspace-deps]` key responsible, e.g. "skip"
+    pub(crate) reason: String,
+    pub(crate) dependencies: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct PackageField {
+    pub(crate) name: String,
+    pub(crate) value: String,
     pub(crate) members: Vec<String>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct PackageConflict {
+    pub(crate) name: String,
+    /// Maps value -> members using it
+    pub(crate) values: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Lints {
+    /// Maps "tool::lint" -> level
+    pub(crate) levels: BTreeMap<String, String>,
+    pub(crate) members: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct LintConflict {
+    pub(crate) member: String,
+    pub(crate) differences: Vec<String>,
+}
+
 impl Output {
     pub(crate) fn new(
         analysis: &DependencyAnalysis,
+        package_analysis: &PackageAnalysis,
+        lint_analysis: &LintAnalysis,
         workspace_root: &str,
         member_count: usize,
     ) -> Self {
@@ -89,7 +182,16 @@ impl Output {
                     package: dep.package.clone(),
                     registry: dep.registry.clone(),
                     default_features: dep.default_features,
+                    path: dep.path.clone(),
+                    git: dep.git.clone(),
+                    features: dep.features.clone(),
                     resolved_from: dep.resolved_from.clone(),
+                    resolution_override: dep.resolution_override,
+                    members_moved: dep.members_moved,
+                    pins: dep.pins.clone(),
+                    diverging: dep.diverging.clone(),
+                    member_changes: dep.member_changes.clone(),
+                    default_features_resolved_from: dep.default_features_resolved_from.clone(),
                 })
                 .collect(),
             conflicts: analysis
@@ -103,13 +205,65 @@ impl Output {
                         .map(|spec| VersionSpec {
                             version: spec.version.clone(),
                             default_features: spec.default_features,
+                            git_reference: spec.git_reference.clone(),
                             members: spec.members.clone(),
                         })
                         .collect(),
                     conflict_types: conflict.conflict_types.clone(),
+                    diagnostics: conflict.diagnostics.clone(),
                 })
                 .collect(),
             unused_workspace_dependencies: analysis.unused_workspace_deps.clone(),
+            demoted_dependencies: analysis
+                .demoted_deps
+                .iter()
+                .map(|dep| DemotedDependency {
+                    name: dep.name.clone(),
+                    members: dep.members.clone(),
+                })
+                .collect(),
+            skipped_by_members: analysis
+                .skipped
+                .iter()
+                .map(|skipped| SkippedDependencies {
+                    member: skipped.member.clone(),
+                    reason: skipped.reason.key(),
+                    dependencies: skipped.dependencies.clone(),
+                })
+                .collect(),
+            common_package_fields: package_analysis
+                .common_fields
+                .iter()
+                .map(|field| PackageField {
+                    name: field.name.clone(),
+                    value: field.value.to_string(),
+                    members: field.members.clone(),
+                })
+                .collect(),
+            package_conflicts: package_analysis
+                .conflicts
+                .iter()
+                .map(|conflict| PackageConflict {
+                    name: conflict.name.clone(),
+                    values: conflict.values.clone(),
+                })
+                .collect(),
+            common_lints: lint_analysis.common_lints.as_ref().map(|lints| Lints {
+                levels: lints
+                    .levels
+                    .iter()
+                    .map(|((tool, lint), level)| (format!("{}::{}", tool, lint), level.clone()))
+                    .collect(),
+                members: lints.members.clone(),
+            }),
+            lint_conflicts: lint_analysis
+                .conflicts
+                .iter()
+                .map(|conflict| LintConflict {
+                    member: conflict.member.clone(),
+                    differences: conflict.differences.clone(),
+                })
+                .collect(),
         }
     }
 
@@ -118,6 +272,37 @@ impl Output {
         self.sort_common_dependencies();
         self.sort_conflicts();
         self.unused_workspace_dependencies.sort();
+        self.demoted_dependencies
+            .sort_by(|a, b| a.name.cmp(&b.name));
+        // Stable, so each member keeps its reasons in the order they were applied
+        self.skipped_by_members
+            .sort_by(|a, b| a.member.cmp(&b.member));
+        self.sort_package_fields();
+        self.sort_lints();
+    }
+
+    /// Sort lint members and conflicts by member name
+    fn sort_lints(&mut self) {
+        if let Some(lints) = &mut self.common_lints {
+            lints.members.sort();
+        }
+        self.lint_conflicts.sort_by(|a, b| a.member.cmp(&b.member));
+    }
+
+    /// Sort package fields and conflicts by name, with sorted members
+    fn sort_package_fields(&mut self) {
+        self.common_package_fields
+            .sort_by(|a, b| a.name.cmp(&b.name));
+        for field in &mut self.common_package_fields {
+            field.members.sort();
+        }
+
+        self.package_conflicts.sort_by(|a, b| a.name.cmp(&b.name));
+        for conflict in &mut self.package_conflicts {
+            for members in conflict.values.values_mut() {
+                members.sort();
+            }
+        }
     }
 
     fn sort_common_dependencies(&mut self) {
@@ -128,8 +313,16 @@ impl Output {
         for dep in &mut self.common_dependencies {
             dep.members.sort();
 
-            // Sort members within resolved_from
-            if let Some(resolved) = &mut dep.resolved_from {
+            // Sort members within resolved_from and default_features_resolved_from
+            for resolved in [
+                dep.resolved_from.as_mut(),
+                dep.default_features_resolved_from.as_mut(),
+                Some(&mut dep.pins),
+                Some(&mut dep.diverging),
+            ]
+            .into_iter()
+            .flatten()
+            {
                 for members in resolved.values_mut() {
                     members.sort();
                 }
@@ -148,6 +341,7 @@ impl Output {
                 a.version
                     .cmp(&b.version)
                     .then_with(|| a.default_features.cmp(&b.default_features))
+                    .then_with(|| a.git_reference.cmp(&b.git_reference))
             });
             for spec in &mut conflict.version_specs {
                 spec.members.sort();
@@ -163,7 +357,7 @@ impl Output {
     }
 
     /// Format as human-readable text
-    pub(crate) fn to_text(&self, resolution_strategy: &VersionResolutionStrategy) -> String {
+    pub(crate) fn to_text(&self, resolver_name: &str) -> String {
         let mut output = String::new();
 
         // Summary
@@ -178,6 +372,28 @@ impl Output {
                 self.summary.conflicts_resolved
             ));
         }
+        let default_features_resolved = self
+            .common_dependencies
+            .iter()
+            .filter(|d| d.default_features_resolved_from.is_some())
+            .count();
+        if default_features_resolved > 0 {
+            output.push_str(&format!(
+                "  {} default-features conflicts resolved\n",
+                default_features_resolved
+            ));
+        }
+        let partially_consolidated = self
+            .common_dependencies
+            .iter()
+            .filter(|d| !d.diverging.is_empty())
+            .count();
+        if partially_consolidated > 0 {
+            output.push_str(&format!(
+                "  {} dependencies with diverging members\n",
+                partially_consolidated
+            ));
+        }
         if self.summary.conflicts_unresolved > 0 {
             output.push_str(&format!(
                 "  {} conflicts could not resolve\n",
@@ -190,6 +406,33 @@ impl Output {
                 self.summary.unused_workspace_deps
             ));
         }
+        if !self.demoted_dependencies.is_empty() {
+            output.push_str(&format!(
+                "  {} workspace dependencies to demote\n",
+                self.demoted_dependencies.len()
+            ));
+        }
+        if !self.common_package_fields.is_empty() {
+            output.push_str(&format!(
+                "  {} package fields to consolidate\n",
+                self.common_package_fields.len()
+            ));
+        }
+        if !self.package_conflicts.is_empty() {
+            output.push_str(&format!(
+                "  {} package fields could not resolve\n",
+                self.package_conflicts.len()
+            ));
+        }
+        if self.common_lints.is_some() {
+            output.push_str("  1 lint table to consolidate\n");
+        }
+        if !self.lint_conflicts.is_empty() {
+            output.push_str(&format!(
+                "  {} members with differing lint tables\n",
+                self.lint_conflicts.len()
+            ));
+        }
         output.push('\n');
 
         // Common dependencies
@@ -197,10 +440,11 @@ impl Output {
             output.push_str("Will consolidate:\n");
             for dep in &self.common_dependencies {
                 output.push_str(&format!(
-                    "  {} = \"{}\" in: {}\n",
+                    "  {} = {} in: {}{}\n",
                     dep.name,
-                    dep.version,
-                    dep.members.join(", ")
+                    format_requirement(dep),
+                    dep.members.join(", "),
+                    format_pins(dep)
                 ));
             }
             output.push('\n');
@@ -212,24 +456,93 @@ impl Output {
                 .filter(|d| d.resolved_from.is_some())
                 .collect();
             if !resolved.is_empty() {
-                output.push_str(&format!(
-                    "Resolved conflicts (using {:?}):\n",
-                    resolution_strategy
-                ));
+                output.push_str(&format!("Resolved conflicts (using {}):\n", resolver_name));
                 for dep in &resolved {
                     if let Some(original_versions) = &dep.resolved_from {
                         let mut versions: Vec<_> = original_versions.keys().collect();
                         versions.sort();
+                        let moved = match dep.members_moved {
+                            1 => "1 member moves".to_string(),
+                            n => format!("{} members move", n),
+                        };
+                        let details = match dep.resolution_override {
+                            Some(strategy) => format!("using {:?}, {}", strategy, moved),
+                            None => moved,
+                        };
                         output.push_str(&format!(
-                            "  {}: {} → {}\n",
+                            "  {}: {} → {} ({})\n",
                             dep.name,
                             versions
                                 .iter()
-                                .map(|s| s.as_str())
+                                .map(|s| format_requirement_list_item(s))
                                 .collect::<Vec<_>>()
                                 .join(", "),
-                            dep.version
+                            dep.version.as_deref().unwrap_or_default(),
+                            details
                         ));
+                        for (change, label) in [
+                            (VersionChange::Upgrade, "upgrade"),
+                            (VersionChange::Downgrade, "downgrade"),
+                            (VersionChange::Breaking, "breaking"),
+                        ] {
+                            let members: Vec<&str> = dep
+                                .member_changes
+                                .iter()
+                                .filter(|(_, c)| **c == change)
+                                .map(|(member, _)| member.as_str())
+                                .collect();
+                            if !members.is_empty() {
+                                output.push_str(&format!(
+                                    "    {}: {}\n",
+                                    label,
+                                    members.join(", ")
+                                ));
+                            }
+                        }
+                    }
+                }
+                output.push('\n');
+            }
+
+            // Resolved default-features conflicts
+            let resolved: Vec<_> = self
+                .common_dependencies
+                .iter()
+                .filter_map(|d| Some((d, d.default_features_resolved_from.as_ref()?)))
+                .collect();
+            if !resolved.is_empty() {
+                output
+                    .push_str("Resolved default-features conflicts (default-features = false):\n");
+                for (dep, original) in &resolved {
+                    match original.get("true") {
+                        Some(members) => output.push_str(&format!(
+                            "  {}: features = [\"default\"] in: {}\n",
+                            dep.name,
+                            members.join(", ")
+                        )),
+                        None => output.push_str(&format!(
+                            "  {}: no member needs default features\n",
+                            dep.name
+                        )),
+                    }
+                }
+                output.push('\n');
+            }
+
+            // Members left on their own version by partial consolidation
+            let diverging: Vec<_> = self
+                .common_dependencies
+                .iter()
+                .filter(|d| !d.diverging.is_empty())
+                .collect();
+            if !diverging.is_empty() {
+                output.push_str("Diverging members (keep their own version):\n");
+                for dep in &diverging {
+                    output.push_str(&format!("  {}:\n", dep.name));
+                    let mut versions: Vec<_> = dep.diverging.iter().collect();
+                    versions.sort();
+                    for (version, members) in versions {
+                        output.push_str(&format!("    {} in: {}\n", version, members.join(", ")));
                     }
                 }
                 output.push('\n');
@@ -249,6 +562,10 @@ impl Output {
                     .map(|ct| match ct {
                         ConflictType::VersionResolution => "version resolution",
                         ConflictType::DefaultFeatures => "default-features differ",
+                        ConflictType::GitReference => "git reference differs",
+                        ConflictType::Prerelease => "only prerelease versions",
+                        ConflictType::ExactPin => "exact pin",
+                        ConflictType::RustVersion => "rust-version",
                     })
                     .collect();
                 let reason = reasons.join(", ");
@@ -261,18 +578,17 @@ impl Output {
                     .contains(&ConflictType::DefaultFeatures);
 
                 for spec in &conflict.version_specs {
-                    let version_display = if has_default_features_conflict {
-                        // Show default-features explicitly when it's part of the conflict
-                        if spec.default_features {
-                            format!("{} (default-features=true)", spec.version)
-                        } else {
-                            format!("{} (default-features=false)", spec.version)
-                        }
-                    } else if !spec.default_features {
-                        format!("{} (default-features=false)", spec.version)
-                    } else {
-                        spec.version.clone()
+                    let mut version_display = match (&spec.version, &spec.git_reference) {
+                        (Some(version), Some(reference)) => format!("{} ({})", version, reference),
+                        (Some(version), None) => version.clone(),
+                        (None, Some(reference)) => reference.to_string(),
+                        (None, None) => "default branch".to_string(),
                     };
+                    // Show default-features explicitly when it's part of the conflict
+                    if has_default_features_conflict || !spec.default_features {
+                        version_display
+                            .push_str(&format!(" (default-features={})", spec.default_features));
+                    }
                     if !spec.members.is_empty() {
                         output.push_str(&format!(
                             "    {} in: {}\n",
@@ -281,6 +597,9 @@ impl Output {
                         ));
                     }
                 }
+                for diagnostic in &conflict.diagnostics {
+                    output.push_str(&format!("    note: {}\n", diagnostic));
+                }
             }
             output.push('\n');
         }
@@ -294,6 +613,144 @@ impl Output {
             output.push('\n');
         }
 
+        // Demoted workspace dependencies
+        if !self.demoted_dependencies.is_empty() {
+            output.push_str("Will demote:\n");
+            for dep in &self.demoted_dependencies {
+                output.push_str(&format!("  {} to: {}\n", dep.name, dep.members.join(", ")));
+            }
+            output.push('\n');
+        }
+
+        // Members opting out through [package.metadata.workspace-deps]
+        if !self.skipped_by_members.is_empty() {
+            output.push_str("Skipped by member metadata:\n");
+            for skipped in &self.skipped_by_members {
+                if skipped.dependencies.is_empty() {
+                    output.push_str(&format!("  {} ({})\n", skipped.member, skipped.reason));
+                } else {
+                    output.push_str(&format!(
+                        "  {}: {} ({})\n",
+                        skipped.member,
+                        skipped.dependencies.join(", "),
+                        skipped.reason
+                    ));
+                }
+            }
+            output.push('\n');
+        }
+
+        // Package fields
+        if !self.common_package_fields.is_empty() {
+            output.push_str("Will consolidate package fields:\n");
+            for field in &self.common_package_fields {
+                output.push_str(&format!(
+                    "  {} = {} in: {}\n",
+                    field.name,
+                    field.value,
+                    field.members.join(", ")
+                ));
+            }
+            output.push('\n');
+        }
+
+        if !self.package_conflicts.is_empty() {
+            output.push_str("Could not resolve package fields:\n");
+            for conflict in &self.package_conflicts {
+                output.push_str(&format!("  {}:\n", conflict.name));
+                for (value, members) in &conflict.values {
+                    output.push_str(&format!("    {} in: {}\n", value, members.join(", ")));
+                }
+            }
+            output.push('\n');
+        }
+
+        // Lints
+        if let Some(lints) = &self.common_lints {
+            output.push_str(&format!(
+                "Will consolidate lints in: {}\n",
+                lints.members.join(", ")
+            ));
+            for (lint, level) in &lints.levels {
+                output.push_str(&format!("  {} = {}\n", lint, level));
+            }
+            output.push('\n');
+        }
+
+        if !self.lint_conflicts.is_empty() {
+            output.push_str("Lint tables differ:\n");
+            for conflict in &self.lint_conflicts {
+                output.push_str(&format!("  {}:\n", conflict.member));
+                for difference in &conflict.differences {
+                    output.push_str(&format!("    {}\n", difference));
+                }
+            }
+            output.push('\n');
+        }
+
         output
     }
 }
+
+/// Describe exact pins of a dependency, e.g. " (pinned by member1)" when the pin
+/// was used for the workspace or " (member1 keeps =1.38.0)" when the member was left out
+fn format_pins(dep: &Dependency) -> String {
+    if dep.pins.is_empty() {
+        return String::new();
+    }
+    let mut pins: Vec<_> = dep.pins.iter().collect();
+    pins.sort();
+    let parts: Vec<String> = pins
+        .into_iter()
+        .map(|(pin, members)| {
+            if dep.version.as_ref() == Some(pin) {
+                format!("pinned by {}", members.join(", "))
+            } else {
+                format!("{} keeps {}", members.join(", "), pin)
+            }
+        })
+        .collect();
+    format!(" ({})", parts.join("; "))
+}
+
+/// Wrap requirements with several comparators (e.g. `>=1, <2`) in parentheses
+/// so they stay readable in a comma-separated list
+fn format_requirement_list_item(requirement: &str) -> String {
+    if requirement.contains(',') {
+        format!("({})", requirement)
+    } else {
+        requirement.to_string()
+    }
+}
+
+/// Format a workspace requirement for text output, e.g. `"1.0"` or
+/// `{ version = "1.0", git = "...", rev = "..." }` for git and path dependencies
+/// and `{ version = "1.0", default-features = false, features = ["derive"] }`
+/// when default features are disabled or features are hoisted
+fn format_requirement(dep: &Dependency) -> String {
+    if dep.path.is_none() && dep.git.is_none() && dep.default_features && dep.features.is_empty() {
+        return format!("\"{}\"", dep.version.as_deref().unwrap_or_default());
+    }
+
+    let mut fields = Vec::new();
+    if let Some(version) = &dep.version {
+        fields.push(format!("version = \"{}\"", version));
+    }
+    if let Some(path) = &dep.path {
+        fields.push(format!("path = \"{}\"", path));
+    }
+    if let Some(git) = &dep.git {
+        fields.push(format!("git = \"{}\"", git.url));
+        if let Some(reference) = &git.reference {
+            fields.push(reference.to_string());
+        }
+    }
+    if !dep.default_features {
+        fields.push("default-features = false".to_string());
+    }
+    if !dep.features.is_empty() {
+        let features: Vec<String> = dep.features.iter().map(|f| format!("\"{}\"", f)).collect();
+        fields.push(format!("features = [{}]", features.join(", ")));
+    }
+    format!("{{ {} }}", fields.join(", "))
+}
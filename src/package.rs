@@ -0,0 +1,186 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+use toml_edit::{Item, Value};
+
+use crate::manifest::{WORKSPACE_MARKER, read_manifest};
+use crate::workspace::WorkspaceInfo;
+
+/// `[package]` fields that can be inherited from `[workspace.package]`
+pub(crate) const INHERITABLE_FIELDS: &[&str] = &[
+    "edition",
+    "rust-version",
+    "license",
+    "repository",
+    "authors",
+    "homepage",
+];
+
+#[derive(Debug, Default)]
+pub(crate) struct PackageAnalysis {
+    /// Fields that will be (or are already) inherited from [workspace.package]
+    pub(crate) common_fields: Vec<CommonPackageField>,
+
+    /// Fields some members set to another value than the one consolidated
+    pub(crate) conflicts: Vec<ConflictingPackageField>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct CommonPackageField {
+    pub(crate) name: String,
+    pub(crate) value: Value,
+    /// Members that will inherit this field
+    pub(crate) members: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ConflictingPackageField {
+    pub(crate) name: String,
+    /// Maps displayed value -> members (and the workspace marker) left on it
+    pub(crate) values: BTreeMap<String, Vec<String>>,
+}
+
+/// Usage of a single `[package]` field across the workspace
+#[derive(Debug, Default)]
+struct FieldUsage {
+    /// Maps displayed value -> (value, members declaring it)
+    values: BTreeMap<String, (Value, Vec<String>)>,
+    /// Value already defined in [workspace.package]
+    workspace_value: Option<Value>,
+}
+
+/// Render a value without its formatting so equivalent values compare equal
+/// Examples: `'MIT'` and `"MIT" # comment` both become `"MIT"`,
+/// `["a","b"]` and `[ "a", "b" ]` both become `["a", "b"]`
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("\"{}\"", s.value()),
+        Value::Array(array) => {
+            let items: Vec<String> = array.iter().map(display_value).collect();
+            format!("[{}]", items.join(", "))
+        }
+        other => {
+            let mut other = other.clone();
+            other.decor_mut().clear();
+            other.to_string()
+        }
+    }
+}
+
+/// Check if a package field uses workspace inheritance (e.g. `edition.workspace = true`)
+pub(crate) fn inherits_workspace(item: &Item) -> bool {
+    item.as_table_like()
+        .is_some_and(|table| table.contains_key("workspace"))
+}
+
+/// Find `[package]` fields shared by members that can move to [workspace.package],
+/// leaving out `skipped_members`
+pub(crate) fn analyze_package_fields(
+    workspace: &WorkspaceInfo,
+    skipped_members: &HashSet<&str>,
+    min_members: usize,
+) -> Result<PackageAnalysis> {
+    let mut usages: BTreeMap<&str, FieldUsage> = BTreeMap::new();
+
+    let root = read_manifest(&workspace.root_manifest)?;
+    if let Some(ws_package) = root
+        .get("workspace")
+        .and_then(|ws| ws.get("package"))
+        .and_then(|p| p.as_table_like())
+    {
+        for field in INHERITABLE_FIELDS {
+            if let Some(value) = ws_package.get(field).and_then(|v| v.as_value()) {
+                usages.entry(field).or_default().workspace_value = Some(value.clone());
+            }
+        }
+    }
+
+    for member in &workspace.members {
+        if skipped_members.contains(member.name.as_str()) {
+            continue;
+        }
+        let doc = read_manifest(&member.manifest_path)?;
+        let Some(package) = doc.get("package").and_then(|p| p.as_table_like()) else {
+            continue;
+        };
+
+        for field in INHERITABLE_FIELDS {
+            let Some(item) = package.get(field) else {
+                continue;
+            };
+            if inherits_workspace(item) {
+                continue;
+            }
+            if let Some(value) = item.as_value() {
+                usages
+                    .entry(field)
+                    .or_default()
+                    .values
+                    .entry(display_value(value))
+                    .or_insert_with(|| (value.clone(), Vec::new()))
+                    .1
+                    .push(member.name.clone());
+            }
+        }
+    }
+
+    let mut analysis = PackageAnalysis::default();
+
+    for (field, usage) in usages {
+        let workspace_shown = usage.workspace_value.as_ref().map(display_value);
+
+        // An existing [workspace.package] value wins, otherwise the value most members agree on
+        let agreed = match &workspace_shown {
+            Some(shown) => Some(shown.clone()),
+            None => {
+                let largest = usage
+                    .values
+                    .values()
+                    .map(|(_, m)| m.len())
+                    .max()
+                    .unwrap_or(0);
+                let mut candidates = usage
+                    .values
+                    .iter()
+                    .filter(|(_, (_, members))| members.len() == largest);
+                match (candidates.next(), candidates.next()) {
+                    (Some((shown, _)), None) if largest >= min_members => Some(shown.clone()),
+                    _ => None,
+                }
+            }
+        };
+
+        // Members on another value keep their own
+        let mut outliers: BTreeMap<String, Vec<String>> = usage
+            .values
+            .iter()
+            .filter(|(shown, _)| agreed.as_ref() != Some(*shown))
+            .map(|(shown, (_, members))| (shown.clone(), members.clone()))
+            .collect();
+        if !outliers.is_empty() && (agreed.is_some() || outliers.len() > 1) {
+            if let Some(shown) = workspace_shown {
+                outliers
+                    .entry(shown)
+                    .or_default()
+                    .push(WORKSPACE_MARKER.to_string());
+            }
+            analysis.conflicts.push(ConflictingPackageField {
+                name: field.to_string(),
+                values: outliers,
+            });
+        }
+
+        let Some((value, members)) = agreed.and_then(|shown| usage.values.get(&shown)) else {
+            // Nothing agreed on, or only defined in [workspace.package]
+            continue;
+        };
+        let mut value = usage.workspace_value.clone().unwrap_or(value.clone());
+        value.decor_mut().clear();
+        analysis.common_fields.push(CommonPackageField {
+            name: field.to_string(),
+            value,
+            members: members.clone(),
+        });
+    }
+
+    Ok(analysis)
+}
@@ -0,0 +1,176 @@
+use anyhow::{Context, Result};
+use semver::Version;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::version_resolver::parse_version_lenient;
+
+/// Version of the index cache format written by Cargo
+const CACHE_VERSION: u8 = 3;
+
+/// A published version of a crate, as recorded in the registry index
+#[derive(Debug, Clone)]
+pub(crate) struct IndexEntry {
+    pub(crate) version: Version,
+    /// Minimum Rust version declared by the crate, if any
+    pub(crate) rust_version: Option<Version>,
+    pub(crate) yanked: bool,
+}
+
+/// One line of an index file, only the fields this tool reads
+#[derive(Debug, Deserialize)]
+struct IndexLine {
+    vers: String,
+    #[serde(default)]
+    yanked: bool,
+    rust_version: Option<String>,
+}
+
+/// Where index files live and how they are encoded
+#[derive(Debug)]
+enum IndexFiles {
+    /// Cargo's cache of a downloaded index, see `parse_cache`
+    Cache(PathBuf),
+    /// One JSON line per version, as in a local registry
+    Plain(PathBuf),
+}
+
+/// Crate index data already on disk, read without touching the network
+#[derive(Debug)]
+pub(crate) struct RegistryIndex {
+    /// Searched in order, the first one that knows a crate wins
+    sources: Vec<IndexFiles>,
+}
+
+impl RegistryIndex {
+    /// Find the crates.io index caches under `$CARGO_HOME` (or `~/.cargo`)
+    pub(crate) fn from_cargo_home() -> Result<Self> {
+        let cargo_home = std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::home_dir().map(|home| home.join(".cargo")))
+            .context("Could not determine the Cargo home directory")?;
+        let index_dir = cargo_home.join("registry").join("index");
+
+        let mut dirs: Vec<PathBuf> = fs::read_dir(&index_dir)
+            .with_context(|| {
+                format!(
+                    "Failed to read the local registry index at {}",
+                    index_dir.display()
+                )
+            })?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name.starts_with("index.crates.io-") || name.starts_with("github.com-")
+                    })
+            })
+            .collect();
+        // The sparse index is what current Cargo versions use, prefer it over the git index
+        dirs.sort_by_key(|dir| !is_sparse(dir));
+
+        if dirs.is_empty() {
+            anyhow::bail!(
+                "No crates.io index found in {}, build the workspace once so Cargo downloads it",
+                index_dir.display()
+            );
+        }
+
+        Ok(RegistryIndex {
+            sources: dirs
+                .into_iter()
+                .map(|dir| IndexFiles::Cache(dir.join(".cache")))
+                .collect(),
+        })
+    }
+
+    /// Use a local registry directory, e.g. one created by `cargo local-registry`,
+    /// or a bare index directory
+    pub(crate) fn open(dir: &Path) -> Result<Self> {
+        if !dir.is_dir() {
+            anyhow::bail!("Registry index {} is not a directory", dir.display());
+        }
+        let index_dir = dir.join("index");
+        let root = if index_dir.is_dir() {
+            index_dir
+        } else {
+            dir.to_path_buf()
+        };
+
+        Ok(RegistryIndex {
+            sources: vec![IndexFiles::Plain(root)],
+        })
+    }
+
+    /// Published versions of a crate, empty if the index has never seen it (or cannot be read)
+    pub(crate) fn versions(&self, name: &str) -> Vec<IndexEntry> {
+        let relative = index_path(name);
+        self.sources
+            .iter()
+            .find_map(|source| source.read(&relative))
+            .unwrap_or_default()
+    }
+}
+
+impl IndexFiles {
+    /// Entries of the index file at `relative`, `None` if there is no such file
+    fn read(&self, relative: &Path) -> Option<Vec<IndexEntry>> {
+        match self {
+            IndexFiles::Cache(root) => Some(parse_cache(&fs::read(root.join(relative)).ok()?)),
+            IndexFiles::Plain(root) => Some(parse_plain(&fs::read(root.join(relative)).ok()?)),
+        }
+    }
+}
+
+fn is_sparse(dir: &Path) -> bool {
+    dir.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("index.crates.io-"))
+}
+
+/// Location of a crate within the index, e.g. `se/rd/serde` or `3/l/log`
+fn index_path(name: &str) -> PathBuf {
+    let name = name.to_lowercase();
+    match name.len() {
+        1 => Path::new("1").join(&name),
+        2 => Path::new("2").join(&name),
+        3 => Path::new("3").join(&name[..1]).join(&name),
+        _ => Path::new(&name[..2]).join(&name[2..4]).join(&name),
+    }
+}
+
+/// Parse a cache file: a version byte, a 4-byte index format version, a NUL-terminated
+/// header and then NUL-separated pairs of version and JSON index line
+/// Unknown cache versions and unreadable lines are skipped rather than treated as errors
+fn parse_cache(content: &[u8]) -> Vec<IndexEntry> {
+    if content.first() != Some(&CACHE_VERSION) || content.len() < 5 {
+        return Vec::new();
+    }
+
+    content[5..]
+        .split(|byte| *byte == 0)
+        // Skip the header and the first version, then take every JSON line
+        .skip(2)
+        .step_by(2)
+        .filter_map(parse_line)
+        .collect()
+}
+
+/// Parse an index file with one JSON line per version
+fn parse_plain(content: &[u8]) -> Vec<IndexEntry> {
+    content
+        .split(|byte| *byte == b'\n')
+        .filter_map(parse_line)
+        .collect()
+}
+
+fn parse_line(line: &[u8]) -> Option<IndexEntry> {
+    let line: IndexLine = serde_json::from_slice(line).ok()?;
+    Some(IndexEntry {
+        version: Version::parse(&line.vers).ok()?,
+        rust_version: line.rust_version.as_deref().and_then(parse_version_lenient),
+        yanked: line.yanked,
+    })
+}
@@ -1,26 +1,93 @@
-use anyhow::{Context, Result};
-use std::fs;
+use anyhow::Result;
 use std::path::Path;
-use toml_edit::{DocumentMut, InlineTable, Item, Table, value};
+use toml_edit::{Array, InlineTable, Item, Table, value};
 
-use crate::dependency::CommonDependency;
+use crate::dependency::{
+    ALL_KINDS, CommonDependency, collect_sections, uses_workspace_inheritance,
+};
+use crate::lints::CommonLints;
+use crate::manifest::read_manifest;
+use crate::package::CommonPackageField;
 
 /// Check if a field should be preserved when converting to workspace dependency
 fn should_preserve_field(key: &str) -> bool {
-    !matches!(key, "version" | "package" | "registry" | "default-features")
+    !matches!(
+        key,
+        "version"
+            | "package"
+            | "registry"
+            | "default-features"
+            | "path"
+            | "git"
+            | "rev"
+            | "tag"
+            | "branch"
+    )
 }
 
-/// Macro to copy preserved fields from an iterator to an inline table
+/// Macro to copy preserved fields from an iterator to an inline table,
+/// dropping features that were hoisted into the workspace entry
 macro_rules! copy_preserved_fields {
-    ($inline:expr, $iter:expr) => {
+    ($inline:expr, $iter:expr, $hoisted:expr) => {
         for (key, val) in $iter {
-            if should_preserve_field(key) {
-                $inline.insert(key, val.clone());
+            if !should_preserve_field(key) {
+                continue;
+            }
+            match (key, val.as_array()) {
+                ("features", Some(features)) if !$hoisted.is_empty() => {
+                    if let Some(remaining) = remaining_features(features, $hoisted) {
+                        $inline.insert(key, remaining);
+                    }
+                }
+                _ => {
+                    $inline.insert(key, val.clone());
+                }
             }
         }
     };
 }
 
+/// Features a member still needs after the hoisted ones moved to the workspace entry
+/// Returns None if nothing is left
+fn remaining_features(features: &Array, hoisted: &[String]) -> Option<toml_edit::Value> {
+    let mut remaining = features.clone();
+    remaining.retain(|feature| {
+        feature
+            .as_str()
+            .is_none_or(|name| !hoisted.iter().any(|h| h == name))
+    });
+    if remaining.is_empty() {
+        return None;
+    }
+    remaining.fmt();
+    Some(remaining.into())
+}
+
+/// Check if a member declaration keeps default features enabled
+fn wants_default_features(item: &Item) -> bool {
+    item.as_table_like()
+        .and_then(|table| table.get("default-features"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+/// Add `"default"` to the features of a member entry
+fn add_default_feature(inline: &mut InlineTable) {
+    const DEFAULT_FEATURE: &str = "default";
+
+    match inline.get_mut("features").and_then(|v| v.as_array_mut()) {
+        Some(features) => {
+            if !features.iter().any(|f| f.as_str() == Some(DEFAULT_FEATURE)) {
+                features.push(DEFAULT_FEATURE);
+            }
+        }
+        None => {
+            let features: Array = [DEFAULT_FEATURE].into_iter().collect();
+            inline.insert("features", features.into());
+        }
+    }
+}
+
 /// Update the [workspace.dependencies] table
 fn update_workspace_deps_table(workspace: &mut Table, deps: &[&CommonDependency]) {
     const WORKSPACE_DEPS_KEY: &str = "dependencies";
@@ -37,10 +104,10 @@ fn update_workspace_deps_table(workspace: &mut Table, deps: &[&CommonDependency]
                 match existing {
                     Item::Table(table) => {
                         for (k, v) in table.iter() {
-                            if should_preserve_field(k) {
-                                if let Some(val) = v.as_value() {
-                                    preserved_fields.push((k.to_string(), val.clone()));
-                                }
+                            if should_preserve_field(k)
+                                && let Some(val) = v.as_value()
+                            {
+                                preserved_fields.push((k.to_string(), val.clone()));
                             }
                         }
                     }
@@ -60,29 +127,47 @@ fn update_workspace_deps_table(workspace: &mut Table, deps: &[&CommonDependency]
             // Only write default-features if false (true is Cargo's default)
             let needs_inline = dep.package.is_some()
                 || dep.registry.is_some()
+                || dep.path.is_some()
+                || dep.git.is_some()
                 || !dep.default_features
+                || !dep.features.is_empty()
                 || !preserved_fields.is_empty();
 
             // Build the value to insert/update
-            let new_value = if needs_inline {
-                let mut inline = InlineTable::new();
-                inline.insert("version", dep.version.as_str().into());
-                if let Some(package) = &dep.package {
-                    inline.insert("package", package.as_str().into());
-                }
-                if let Some(registry) = &dep.registry {
-                    inline.insert("registry", registry.as_str().into());
-                }
-                if !dep.default_features {
-                    inline.insert("default-features", false.into());
-                }
-                // Add preserved fields from existing entry
-                for (k, v) in preserved_fields {
-                    inline.insert(&k, v);
+            let new_value = match &dep.version {
+                Some(version) if !needs_inline => value(version),
+                _ => {
+                    let mut inline = InlineTable::new();
+                    if let Some(version) = &dep.version {
+                        inline.insert("version", version.as_str().into());
+                    }
+                    if let Some(path) = &dep.path {
+                        inline.insert("path", path.as_str().into());
+                    }
+                    if let Some(git) = &dep.git {
+                        inline.insert("git", git.url.as_str().into());
+                        if let Some(reference) = &git.reference {
+                            inline.insert(reference.key(), reference.value().into());
+                        }
+                    }
+                    if let Some(package) = &dep.package {
+                        inline.insert("package", package.as_str().into());
+                    }
+                    if let Some(registry) = &dep.registry {
+                        inline.insert("registry", registry.as_str().into());
+                    }
+                    if !dep.default_features {
+                        inline.insert("default-features", false.into());
+                    }
+                    // Add preserved fields from existing entry
+                    for (k, v) in preserved_fields {
+                        inline.insert(&k, v);
+                    }
+                    if !dep.features.is_empty() {
+                        inline.insert("features", dep.features.iter().collect::<Array>().into());
+                    }
+                    value(inline)
                 }
-                value(inline)
-            } else {
-                value(&dep.version)
             };
 
             // Check if entry already exists
@@ -100,12 +185,7 @@ pub(crate) fn update_workspace_dependencies(
     manifest_path: &Path,
     common_deps: &[CommonDependency],
 ) -> Result<String> {
-    let content = fs::read_to_string(manifest_path)
-        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
-
-    let mut doc = content
-        .parse::<DocumentMut>()
-        .with_context(|| format!("Failed to parse TOML at {}", manifest_path.display()))?;
+    let mut doc = read_manifest(manifest_path)?;
 
     if !doc.contains_key("workspace") {
         doc["workspace"] = Item::Table(Table::new());
@@ -131,12 +211,7 @@ pub(crate) fn update_member_dependencies(
     common_deps: &[CommonDependency],
     member_name: &str,
 ) -> Result<String> {
-    let content = fs::read_to_string(manifest_path)
-        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
-
-    let mut doc = content
-        .parse::<DocumentMut>()
-        .with_context(|| format!("Failed to parse TOML at {}", manifest_path.display()))?;
+    let mut doc = read_manifest(manifest_path)?;
 
     for dep in common_deps {
         // Find sections this member uses for this dependency
@@ -144,7 +219,7 @@ pub(crate) fn update_member_dependencies(
             .members
             .iter()
             .filter(|(name, _)| name == member_name)
-            .map(|(_, section)| *section)
+            .map(|(_, section)| section.clone())
             .collect();
 
         if member_sections.is_empty() {
@@ -152,13 +227,12 @@ pub(crate) fn update_member_dependencies(
         }
 
         for section in member_sections {
-            let section_key = section.as_str();
-
-            if let Some(Item::Table(section_table)) = doc.get_mut(section_key)
+            if let Some(section_table) = section.table_mut(&mut doc)
                 && let Some(existing) = section_table.get(&dep.name)
             {
                 let mut inline = InlineTable::new();
                 inline.insert("workspace", true.into());
+                let restore_defaults = !dep.default_features && wants_default_features(existing);
 
                 // Preserve fields like features, optional, etc.
                 match existing {
@@ -167,17 +241,23 @@ pub(crate) fn update_member_dependencies(
                             inline,
                             table
                                 .iter()
-                                .filter_map(|(k, v)| v.as_value().map(|val| (k, val)))
+                                .filter_map(|(k, v)| v.as_value().map(|val| (k, val))),
+                            &dep.features
                         );
                     }
                     Item::Value(val) if val.is_inline_table() => {
                         if let Some(table) = val.as_inline_table() {
-                            copy_preserved_fields!(inline, table.iter());
+                            copy_preserved_fields!(inline, table.iter(), &dep.features);
                         }
                     }
                     _ => {}
                 }
 
+                // The workspace entry disables default features, so ask for them explicitly
+                if restore_defaults {
+                    add_default_feature(&mut inline);
+                }
+
                 section_table[&dep.name] = value(inline);
             }
         }
@@ -185,3 +265,300 @@ pub(crate) fn update_member_dependencies(
 
     Ok(doc.to_string())
 }
+
+/// A [workspace.dependencies] entry to copy back into the members inheriting it
+#[derive(Debug)]
+pub(crate) struct InlinedDependency {
+    pub(crate) name: String,
+    /// Fields of the workspace entry, e.g. `version` for `serde = "1.0"`
+    fields: Vec<(String, toml_edit::Value)>,
+}
+
+/// Fields of a dependency entry, a bare version string becoming `version`
+fn entry_fields(item: &Item) -> Vec<(String, toml_edit::Value)> {
+    match item {
+        Item::Value(val) if val.is_str() => vec![("version".to_string(), val.clone())],
+        _ => item
+            .as_table_like()
+            .map(|table| {
+                table
+                    .iter()
+                    .filter_map(|(k, v)| Some((k.to_string(), v.as_value()?.clone())))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// Entries of [workspace.dependencies] whose name matches one of the patterns
+/// Fails if a pattern matches no entry
+pub(crate) fn workspace_dependency_entries(
+    manifest_path: &Path,
+    patterns: &[glob::Pattern],
+) -> Result<Vec<InlinedDependency>> {
+    let doc = read_manifest(manifest_path)?;
+
+    let entries: Vec<InlinedDependency> = doc
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(|d| d.as_table_like())
+        .into_iter()
+        .flat_map(|table| table.iter())
+        .filter(|(name, _)| patterns.iter().any(|pattern| pattern.matches(name)))
+        .map(|(name, item)| InlinedDependency {
+            name: name.to_string(),
+            fields: entry_fields(item),
+        })
+        .collect();
+
+    for pattern in patterns {
+        if !entries.iter().any(|entry| pattern.matches(&entry.name)) {
+            anyhow::bail!("No entry in [workspace.dependencies] matches '{}'", pattern);
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Merge a workspace entry into the member entry inheriting it
+/// Member fields win, except `features` which are added to the inherited ones
+fn inline_entry(
+    entry: &InlinedDependency,
+    member: &Item,
+    rebase_path: impl Fn(&str) -> String,
+) -> Item {
+    let mut inline = InlineTable::new();
+    for (key, val) in &entry.fields {
+        let mut val = match (key.as_str(), val.as_str()) {
+            ("path", Some(path)) => rebase_path(path).into(),
+            _ => val.clone(),
+        };
+        val.decor_mut().clear();
+        inline.insert(key, val);
+    }
+
+    for (key, val) in entry_fields(member) {
+        if key == "workspace" {
+            continue;
+        }
+        if key == "features"
+            && let (Some(features), Some(inherited)) = (
+                val.as_array(),
+                inline.get_mut("features").and_then(|v| v.as_array_mut()),
+            )
+        {
+            for feature in features.iter().filter_map(|f| f.as_str()) {
+                if !inherited.iter().any(|f| f.as_str() == Some(feature)) {
+                    inherited.push(feature);
+                }
+            }
+            inherited.fmt();
+            continue;
+        }
+        inline.insert(&key, val);
+    }
+
+    // A plain version is written the short way, e.g. `serde = "1.0"`
+    if inline.len() == 1
+        && let Some(version) = inline.get("version").and_then(|v| v.as_str())
+    {
+        return value(version);
+    }
+    value(inline)
+}
+
+/// Replace a member's `workspace = true` entries with the fields of the workspace entry
+/// `rebase_path` turns a path relative to the workspace root into one relative to the member
+/// Returns the updated manifest and the names of the inlined dependencies
+pub(crate) fn inline_member_dependencies(
+    manifest_path: &Path,
+    entries: &[InlinedDependency],
+    rebase_path: impl Fn(&str) -> String,
+) -> Result<(String, Vec<String>)> {
+    let mut doc = read_manifest(manifest_path)?;
+
+    let mut inlined = Vec::new();
+    for section in collect_sections(&doc, ALL_KINDS) {
+        let Some(section_table) = section.table_mut(&mut doc) else {
+            continue;
+        };
+        for entry in entries {
+            let Some(existing) = section_table
+                .get(&entry.name)
+                .filter(|item| uses_workspace_inheritance(item))
+            else {
+                continue;
+            };
+            section_table[&entry.name] = inline_entry(entry, existing, &rebase_path);
+            inlined.push(entry.name.clone());
+        }
+    }
+    inlined.sort();
+    inlined.dedup();
+
+    Ok((doc.to_string(), inlined))
+}
+
+/// Remove entries from [workspace.dependencies] in the root Cargo.toml
+pub(crate) fn remove_workspace_dependencies(
+    manifest_path: &Path,
+    names: &[String],
+) -> Result<String> {
+    let mut doc = read_manifest(manifest_path)?;
+
+    if let Some(deps_table) = doc
+        .get_mut("workspace")
+        .and_then(|w| w.get_mut("dependencies"))
+        .and_then(|d| d.as_table_like_mut())
+    {
+        for name in names {
+            deps_table.remove(name);
+        }
+    }
+
+    Ok(doc.to_string())
+}
+
+/// Add or update [workspace.package] fields in the root Cargo.toml
+pub(crate) fn update_workspace_package(
+    manifest_path: &Path,
+    common_fields: &[CommonPackageField],
+) -> Result<String> {
+    let mut doc = read_manifest(manifest_path)?;
+
+    if !doc.contains_key("workspace") {
+        doc["workspace"] = Item::Table(Table::new());
+    }
+
+    let Some(Item::Table(workspace)) = doc.get_mut("workspace") else {
+        anyhow::bail!("Failed to get workspace table");
+    };
+
+    if !workspace.contains_key("package") {
+        workspace["package"] = Item::Table(Table::new());
+    }
+
+    let Some(Item::Table(package_table)) = workspace.get_mut("package") else {
+        anyhow::bail!("Failed to get workspace.package table");
+    };
+
+    for field in common_fields {
+        if let Some(existing) = package_table.get_mut(&field.name) {
+            *existing = Item::Value(field.value.clone());
+        } else {
+            package_table.insert(&field.name, Item::Value(field.value.clone()));
+        }
+    }
+
+    Ok(doc.to_string())
+}
+
+/// Update a member's [package] fields to inherit from [workspace.package]
+pub(crate) fn update_member_package(
+    manifest_path: &Path,
+    common_fields: &[CommonPackageField],
+    member_name: &str,
+) -> Result<String> {
+    let mut doc = read_manifest(manifest_path)?;
+
+    let Some(Item::Table(package_table)) = doc.get_mut("package") else {
+        return Ok(doc.to_string());
+    };
+
+    for field in common_fields {
+        if !field.members.iter().any(|name| name == member_name) {
+            continue;
+        }
+
+        if let Some(existing) = package_table.get_mut(&field.name) {
+            // Written as a dotted key, e.g. `edition.workspace = true`,
+            // keeping any trailing comment
+            let mut flag = toml_edit::Value::from(true);
+            if let Some(suffix) = existing.as_value().and_then(|v| v.decor().suffix()) {
+                flag.decor_mut().set_suffix(suffix.clone());
+            }
+            let mut inline = InlineTable::new();
+            inline.insert("workspace", flag);
+            inline.set_dotted(true);
+            *existing = value(inline);
+        }
+    }
+
+    Ok(doc.to_string())
+}
+
+/// Add [workspace.lints] to the root Cargo.toml if it is not defined yet
+pub(crate) fn update_workspace_lints(
+    manifest_path: &Path,
+    common_lints: &CommonLints,
+) -> Result<String> {
+    let mut doc = read_manifest(manifest_path)?;
+
+    if !doc.contains_key("workspace") {
+        doc["workspace"] = Item::Table(Table::new());
+    }
+
+    let Some(Item::Table(workspace)) = doc.get_mut("workspace") else {
+        anyhow::bail!("Failed to get workspace table");
+    };
+
+    // An existing [workspace.lints] is the agreed table, leave it as is
+    if workspace.contains_key("lints") {
+        return Ok(doc.to_string());
+    }
+
+    let mut lints_table = Table::new();
+    lints_table.set_implicit(true);
+    for (tool, lints) in &common_lints.lints {
+        let mut tool_table = Table::new();
+        for (lint, level) in lints {
+            let mut level = level.clone();
+            level.decor_mut().clear();
+            tool_table.insert(lint, Item::Value(level));
+        }
+        lints_table.insert(tool, Item::Table(tool_table));
+    }
+    workspace.insert("lints", Item::Table(lints_table));
+
+    Ok(doc.to_string())
+}
+
+/// Replace a member's [lints] table with `workspace = true`
+pub(crate) fn update_member_lints(
+    manifest_path: &Path,
+    common_lints: &CommonLints,
+    member_name: &str,
+) -> Result<String> {
+    let mut doc = read_manifest(manifest_path)?;
+
+    if !common_lints.members.iter().any(|name| name == member_name) {
+        return Ok(doc.to_string());
+    }
+
+    let Some(Item::Table(lints_table)) = doc.get_mut("lints") else {
+        return Ok(doc.to_string());
+    };
+
+    // Keep the table where it was, e.g. where `[lints.clippy]` was written
+    let position = lints_table.position().or_else(|| {
+        lints_table
+            .iter()
+            .filter_map(|(_, item)| item.as_table().and_then(|t| t.position()))
+            .min()
+    });
+    let prefix = lints_table.decor().prefix().cloned().or_else(|| {
+        lints_table
+            .iter()
+            .find_map(|(_, item)| item.as_table().and_then(|t| t.decor().prefix().cloned()))
+    });
+
+    lints_table.clear();
+    lints_table.set_implicit(false);
+    lints_table.set_position(position);
+    if let Some(prefix) = prefix {
+        lints_table.decor_mut().set_prefix(prefix);
+    }
+    lints_table.insert("workspace", value(true));
+
+    Ok(doc.to_string())
+}
@@ -1,12 +1,14 @@
 use anyhow::Result;
-use semver::{Version, VersionReq};
-use std::collections::HashMap;
+use semver::{Comparator, Op, Version, VersionReq};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-use crate::VersionResolutionStrategy;
+use crate::registry::IndexEntry;
+use crate::{PrereleasePolicy, VersionFormat, VersionResolutionStrategy};
 
 /// Parse a version string leniently, normalizing to valid semver if needed
 /// Examples: "1.0" -> "1.0.0", "2" -> "2.0.0"
-fn parse_version_lenient(v: &str) -> Option<Version> {
+pub(crate) fn parse_version_lenient(v: &str) -> Option<Version> {
     // Try parsing as-is first
     if let Ok(version) = Version::parse(v) {
         return Some(version);
@@ -23,107 +25,796 @@ fn parse_version_lenient(v: &str) -> Option<Version> {
     Version::parse(&normalized).ok()
 }
 
-/// Resolve version conflicts using the specified strategy
-/// Returns (resolved_version, all_members) or error if can't resolve
-pub(crate) fn resolve_version_conflict(
-    version_map: &HashMap<String, Vec<String>>,
-    strategy: &VersionResolutionStrategy,
-) -> Result<(String, Vec<String>)> {
-    let all_members: Vec<String> = version_map.values().flatten().cloned().collect();
+/// Range of versions accepted by a requirement: `lower <= version < upper`
+/// Prerelease ordering is approximated, e.g. `<=1.2.3` becomes `<1.2.4`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct VersionRange {
+    pub(crate) lower: Version,
+    /// Exclusive upper bound, `None` if unbounded
+    pub(crate) upper: Option<Version>,
+}
 
-    let versions: Vec<String> = version_map.keys().cloned().collect();
+impl VersionRange {
+    const ANY: VersionRange = VersionRange {
+        lower: Version::new(0, 0, 0),
+        upper: None,
+    };
 
-    match strategy {
-        VersionResolutionStrategy::Skip => {
-            anyhow::bail!("Skip strategy")
+    /// Overlap of two ranges, `None` if they are disjoint
+    pub(crate) fn intersect(&self, other: &VersionRange) -> Option<VersionRange> {
+        let lower = self.lower.clone().max(other.lower.clone());
+        let upper = match (&self.upper, &other.upper) {
+            (Some(a), Some(b)) => Some(a.clone().min(b.clone())),
+            (a, b) => a.clone().or(b.clone()),
+        };
+        match &upper {
+            Some(upper) if *upper <= lower => None,
+            _ => Some(VersionRange { lower, upper }),
         }
-        VersionResolutionStrategy::Fail => {
-            anyhow::bail!("Version conflict detected with fail strategy")
+    }
+
+    /// Range accepted by a single comparator
+    fn from_comparator(cmp: &Comparator) -> VersionRange {
+        let Comparator {
+            major,
+            minor,
+            patch,
+            ..
+        } = *cmp;
+        let mut lower = Version::new(major, minor.unwrap_or(0), patch.unwrap_or(0));
+        lower.pre = cmp.pre.clone();
+
+        // Version right after the most specific component that was written
+        let next = match (minor, patch) {
+            (Some(minor), Some(patch)) => Version::new(major, minor, patch + 1),
+            (Some(minor), None) => Version::new(major, minor + 1, 0),
+            _ => Version::new(major + 1, 0, 0),
+        };
+        // Upper bound of a caret requirement: the next incompatible version
+        let caret_upper = match (major, minor, patch) {
+            (0, Some(0), Some(patch)) => Version::new(0, 0, patch + 1),
+            (0, Some(minor), _) => Version::new(0, minor + 1, 0),
+            _ => Version::new(major + 1, 0, 0),
+        };
+        let below = |upper: Version| VersionRange {
+            lower: Version::new(0, 0, 0),
+            upper: Some(upper),
+        };
+
+        match cmp.op {
+            Op::Exact | Op::Wildcard => VersionRange {
+                lower,
+                upper: Some(next),
+            },
+            Op::Greater => VersionRange {
+                lower: next,
+                upper: None,
+            },
+            Op::GreaterEq => VersionRange { lower, upper: None },
+            Op::Less => below(Version::new(major, minor.unwrap_or(0), patch.unwrap_or(0))),
+            Op::LessEq => below(next),
+            Op::Tilde => VersionRange {
+                lower,
+                upper: Some(match minor {
+                    Some(minor) => Version::new(major, minor + 1, 0),
+                    None => Version::new(major + 1, 0, 0),
+                }),
+            },
+            Op::Caret => VersionRange {
+                lower,
+                upper: Some(caret_upper),
+            },
+            _ => VersionRange::ANY,
         }
-        VersionResolutionStrategy::Highest => resolve_highest(&versions, all_members),
-        VersionResolutionStrategy::Lowest => resolve_lowest(&versions, all_members),
-        VersionResolutionStrategy::HighestCompatible => {
-            resolve_highest_compatible(&versions, all_members)
+    }
+
+    /// Range accepted by a parsed requirement, `None` if it matches no version
+    fn from_req(req: &VersionReq) -> Option<VersionRange> {
+        req.comparators
+            .iter()
+            .map(VersionRange::from_comparator)
+            .try_fold(VersionRange::ANY, |range, next| range.intersect(&next))
+    }
+
+    /// Shortest requirement accepting exactly this range, writing the lower bound
+    /// with `precision` components when possible
+    /// e.g. `[1.4.1, 2.0.0)` -> `1.4.1`, `[1.4.1, 1.5.0)` -> `~1.4.1`
+    fn to_requirement(&self, precision: usize) -> String {
+        let short = format_version(&self.lower, precision);
+        let full = self.lower.to_string();
+        let mut candidates = Vec::new();
+        for lower in [&short, &full] {
+            candidates.push(lower.clone());
+            candidates.push(format!("~{}", lower));
+            candidates.push(format!("={}", lower));
+        }
+        if let Some(upper) = &self.upper {
+            let upper = format_version(upper, 1);
+            candidates.push(format!("<{}", upper));
+            candidates.push(format!(">={}, <{}", short, upper));
+        } else {
+            candidates.push("*".to_string());
+            candidates.push(format!(">={}", short));
         }
+
+        candidates
+            .into_iter()
+            .find(|candidate| {
+                VersionReq::parse(candidate)
+                    .ok()
+                    .and_then(|req| VersionRange::from_req(&req))
+                    .is_some_and(|range| range == *self)
+            })
+            .unwrap_or_else(|| match &self.upper {
+                Some(upper) => format!(">={}, <{}", full, upper),
+                None => format!(">={}", full),
+            })
     }
 }
 
-/// Find and return version by order (highest or lowest)
-fn resolve_by_order(
-    versions: &[String],
-    members: Vec<String>,
-    take_last: bool,
-) -> Result<(String, Vec<String>)> {
-    let mut parsed: Vec<Version> = versions
+/// Write a version with at least `precision` components, dropping trailing zeros
+/// beyond that, e.g. `1.2.0` with precision 2 -> `1.2`
+/// Versions with prerelease or build metadata are always written in full
+pub(crate) fn format_version(version: &Version, precision: usize) -> String {
+    if !version.pre.is_empty() || !version.build.is_empty() {
+        return version.to_string();
+    }
+    let components = [version.major, version.minor, version.patch];
+    let len = components
         .iter()
-        .filter_map(|v| parse_version_lenient(v))
-        .collect();
+        .rposition(|c| *c != 0)
+        .map_or(1, |i| i + 1)
+        .max(precision.clamp(1, 3));
+    components[..len]
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
 
-    anyhow::ensure!(!parsed.is_empty(), "No valid semver versions found");
+/// Rewrite a bare (or caret) version requirement according to the format policy
+/// e.g. "1.2.3" -> "1.2" with `MajorMinor`, "0.3.4" stays "0.3" with `Major`
+/// Other requirement forms and prerelease versions are returned unchanged
+pub(crate) fn format_requirement(requirement: &str, format: &VersionFormat) -> String {
+    let (prefix, bare) = match requirement.strip_prefix('^') {
+        Some(bare) => ("^", bare),
+        None => ("", requirement),
+    };
+    let version = match parse_version_lenient(bare) {
+        Some(version) if version.pre.is_empty() && version.build.is_empty() => version,
+        _ => return requirement.to_string(),
+    };
 
-    parsed.sort();
-    let version = if take_last {
-        parsed.last()
-    } else {
-        parsed.first()
+    let precision = match format {
+        VersionFormat::AsWritten => return requirement.to_string(),
+        VersionFormat::Major => 1,
+        VersionFormat::MajorMinor => 2,
+        VersionFormat::Full => 3,
+    };
+    // Dropping the first non-zero component would widen the compatible range
+    let compatible = match (version.major, version.minor) {
+        (0, 0) => 3,
+        (0, _) => 2,
+        _ => 1,
+    };
+    let components = [version.major, version.minor, version.patch];
+    let formatted = components[..precision.max(compatible)]
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(".");
+    format!("{}{}", prefix, formatted)
+}
+
+/// A requirement as written by members, e.g. `~1.2` or `>=1, <2`
+#[derive(Debug)]
+struct Requirement<'a> {
+    raw: &'a str,
+    req: VersionReq,
+    range: VersionRange,
+    members: &'a [String],
+}
+
+impl<'a> Requirement<'a> {
+    fn parse(raw: &'a str, members: &'a [String]) -> Result<Self> {
+        let req = VersionReq::parse(raw).map_err(|e| {
+            anyhow::anyhow!(
+                "Invalid requirement '{}' in {}: {}",
+                raw,
+                members.join(", "),
+                e
+            )
+        })?;
+        let range = VersionRange::from_req(&req).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Requirement '{}' in {} matches no version",
+                raw,
+                members.join(", ")
+            )
+        })?;
+        Ok(Requirement {
+            raw,
+            req,
+            range,
+            members,
+        })
     }
-    .unwrap();
-    Ok((version.to_string(), members))
-}
-
-/// Find and return the highest version
-fn resolve_highest(versions: &[String], members: Vec<String>) -> Result<(String, Vec<String>)> {
-    resolve_by_order(versions, members, true)
-}
-
-/// Find and return the lowest version
-fn resolve_lowest(versions: &[String], members: Vec<String>) -> Result<(String, Vec<String>)> {
-    resolve_by_order(versions, members, false)
-}
-
-/// Find highest version that satisfies all requirements
-fn resolve_highest_compatible(
-    versions: &[String],
-    members: Vec<String>,
-) -> Result<(String, Vec<String>)> {
-    // Parse as requirements (e.g., "1.0" -> "^1.0")
-    let mut reqs = Vec::new();
-    for v in versions {
-        let req = match VersionReq::parse(v) {
-            Ok(r) => r,
-            Err(_) => {
-                // If it's a valid version (with lenient parsing), create a caret req
-                if let Some(version) = parse_version_lenient(v) {
-                    VersionReq::parse(&format!("^{}", version)).map_err(|e| {
-                        anyhow::anyhow!("Failed to parse version requirement: {}", e)
-                    })?
-                } else {
-                    anyhow::bail!("Invalid version: {}", v);
-                }
+
+    /// Lowest version this requirement accepts, used as a resolution candidate
+    fn minimum(&self) -> &Version {
+        &self.range.lower
+    }
+
+    /// Number of components the member wrote for the minimum version,
+    /// e.g. 2 for `1.0` and `~1.2`, 3 when the minimum was not written directly (`>1.2`)
+    fn precision(&self) -> usize {
+        match self.req.comparators.first() {
+            Some(cmp)
+                if matches!(
+                    cmp.op,
+                    Op::Exact | Op::Wildcard | Op::GreaterEq | Op::Tilde | Op::Caret
+                ) =>
+            {
+                1 + usize::from(cmp.minor.is_some()) + usize::from(cmp.patch.is_some())
             }
-        };
-        reqs.push(req);
+            _ => 3,
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!("'{}' ({})", self.raw, self.members.join(", "))
     }
+}
+
+/// Parse every requirement of a version map, sorted for deterministic diagnostics
+fn parse_requirements(version_map: &HashMap<String, Vec<String>>) -> Result<Vec<Requirement<'_>>> {
+    let mut entries: Vec<_> = version_map.iter().collect();
+    entries.sort();
+    let requirements = entries
+        .into_iter()
+        .map(|(raw, members)| Requirement::parse(raw, members))
+        .collect::<Result<Vec<_>>>()?;
+
+    anyhow::ensure!(!requirements.is_empty(), "No valid semver versions found");
+    Ok(requirements)
+}
+
+/// Describe pairs of requirements that no version can satisfy together
+pub(crate) fn incompatible_pairs(version_map: &HashMap<String, Vec<String>>) -> Vec<String> {
+    let Ok(requirements) = parse_requirements(version_map) else {
+        return Vec::new();
+    };
+
+    let mut pairs = Vec::new();
+    for (i, a) in requirements.iter().enumerate() {
+        for b in &requirements[i + 1..] {
+            if a.range.intersect(&b.range).is_none() {
+                pairs.push(format!(
+                    "{} and {} do not overlap",
+                    a.describe(),
+                    b.describe()
+                ));
+            }
+        }
+    }
+    pairs
+}
+
+/// How a member's requirement changes when it moves to the workspace version
+/// Ordered by severity, so a member using the crate in several sections gets the worst one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum VersionChange {
+    Unchanged,
+    Upgrade,
+    Downgrade,
+    Breaking,
+}
+
+/// Leftmost non-zero component of a version, the part SemVer compatible versions share
+fn compatibility_class(version: &Version) -> (u64, u64, u64) {
+    match (version.major, version.minor) {
+        (0, 0) => (0, 0, version.patch),
+        (0, minor) => (0, minor, 0),
+        (major, _) => (major, 0, 0),
+    }
+}
+
+/// Whether two versions are SemVer compatible, i.e. share the leftmost non-zero component
+fn is_compatible(a: &Version, b: &Version) -> bool {
+    compatibility_class(a) == compatibility_class(b)
+}
+
+/// Requirements of the largest group whose minimum versions are SemVer compatible,
+/// counting each user once (the workspace entry included). Ties go to the newest group
+/// Every requirement is kept if one of them cannot be parsed
+pub(crate) fn largest_compatible_group(
+    version_map: &HashMap<String, Vec<String>>,
+) -> HashSet<String> {
+    let Ok(requirements) = parse_requirements(version_map) else {
+        return version_map.keys().cloned().collect();
+    };
+
+    let mut groups: BTreeMap<(u64, u64, u64), Vec<&Requirement>> = BTreeMap::new();
+    for requirement in &requirements {
+        groups
+            .entry(compatibility_class(requirement.minimum()))
+            .or_default()
+            .push(requirement);
+    }
+
+    let users = |group: &[&Requirement]| {
+        group
+            .iter()
+            .flat_map(|r| r.members)
+            .collect::<HashSet<_>>()
+            .len()
+    };
+    // max_by_key returns the last maximum, i.e. the newest group
+    groups
+        .values()
+        .max_by_key(|group| users(group))
+        .into_iter()
+        .flatten()
+        .map(|r| r.raw.to_string())
+        .collect()
+}
+
+/// Classify moving from `original` to `resolved` by comparing the lowest version each accepts
+/// Returns `None` if either requirement cannot be parsed
+pub(crate) fn classify_change(original: &str, resolved: &str) -> Option<VersionChange> {
+    let range = |raw: &str| VersionRange::from_req(&VersionReq::parse(raw).ok()?);
+    let original = range(original)?;
+    let resolved = range(resolved)?;
+    let (old, new) = (&original.lower, &resolved.lower);
+    // Staying inside the original requirement is never breaking, e.g. `*` -> `0.4.20`
+    let accepted = new >= old && original.upper.as_ref().is_none_or(|upper| new < upper);
+
+    Some(if new == old {
+        VersionChange::Unchanged
+    } else if !accepted && !is_compatible(old, new) {
+        VersionChange::Breaking
+    } else if new > old {
+        VersionChange::Upgrade
+    } else {
+        VersionChange::Downgrade
+    })
+}
 
-    let mut candidates: Vec<Version> = versions
+/// Whether a requirement names a prerelease, e.g. `1.0.0-alpha` or `>=2.0.0-rc.1, <3`
+pub(crate) fn is_prerelease(requirement: &str) -> bool {
+    VersionReq::parse(requirement)
+        .is_ok_and(|req| req.comparators.iter().any(|cmp| !cmp.pre.is_empty()))
+}
+
+/// Whether a requirement pins one exact version, e.g. `=1.38.0`
+pub(crate) fn is_exact_pin(requirement: &str) -> bool {
+    VersionReq::parse(requirement).is_ok_and(|req| {
+        matches!(req.comparators.as_slice(), [cmp] if cmp.op == Op::Exact && cmp.patch.is_some())
+    })
+}
+
+/// Requirements the strategies may pick from: unless prereleases are allowed,
+/// they are dropped whenever a stable requirement is left
+pub(crate) fn apply_prerelease_policy(
+    version_map: &HashMap<String, Vec<String>>,
+    policy: &PrereleasePolicy,
+) -> HashMap<String, Vec<String>> {
+    let stable: HashMap<String, Vec<String>> = version_map
         .iter()
-        .filter_map(|v| parse_version_lenient(v))
+        .filter(|(raw, _)| !is_prerelease(raw))
+        .map(|(raw, members)| (raw.clone(), members.clone()))
+        .collect();
+
+    if *policy == PrereleasePolicy::Allow || stable.is_empty() {
+        version_map.clone()
+    } else {
+        stable
+    }
+}
+
+/// rust-version needed by a requirement: that of the oldest published, non-yanked
+/// version it accepts, `None` if the index knows no such version or it declares none
+pub(crate) fn required_rust_version<'a>(
+    requirement: &str,
+    published: &'a [IndexEntry],
+) -> Option<&'a Version> {
+    let req = VersionReq::parse(requirement).ok()?;
+    published
+        .iter()
+        .filter(|entry| !entry.yanked && req.matches(&entry.version))
+        .min_by(|a, b| a.version.cmp(&b.version))?
+        .rust_version
+        .as_ref()
+}
+
+/// Requirements the strategies may pick from when the workspace builds with `msrv`
+/// Requirements the index knows nothing about are kept
+pub(crate) fn apply_msrv(
+    version_map: &HashMap<String, Vec<String>>,
+    published: &[IndexEntry],
+    msrv: &Version,
+) -> HashMap<String, Vec<String>> {
+    version_map
+        .iter()
+        .filter(|(raw, _)| required_rust_version(raw, published).is_none_or(|rust| rust <= msrv))
+        .map(|(raw, members)| (raw.clone(), members.clone()))
+        .collect()
+}
+
+/// Requirements of `version_map` the policies left out of `candidates` that cannot
+/// move to `resolved` without a breaking change, sorted
+pub(crate) fn breaking_exclusions(
+    version_map: &HashMap<String, Vec<String>>,
+    candidates: &HashMap<String, Vec<String>>,
+    resolved: &str,
+) -> Vec<String> {
+    let mut breaking: Vec<String> = version_map
+        .keys()
+        .filter(|raw| !candidates.contains_key(*raw))
+        .filter(|raw| classify_change(raw, resolved) == Some(VersionChange::Breaking))
+        .cloned()
         .collect();
+    breaking.sort();
+    breaking
+}
+
+/// A dependency whose members disagree on the version requirement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyKey {
+    /// Name the dependency is declared under
+    pub name: String,
+    /// Crate name, when the dependency is renamed with `package = "..."`
+    pub package: Option<String>,
+    /// Alternative registry, `None` for crates.io
+    pub registry: Option<String>,
+}
+
+/// What a [`VersionResolver`] decided for a dependency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// Move every member onto this requirement
+    Resolved(String),
+    /// Report the dependency as a conflict, with an optional note explaining why
+    Conflict(Option<String>),
+}
+
+/// Picks the requirement written to [workspace.dependencies] when members disagree
+pub trait VersionResolver {
+    /// Name shown in the report, e.g. "Resolved conflicts (using HighestCompatible)"
+    fn name(&self) -> String;
+
+    /// `versions` maps each requirement to the members using it, an existing
+    /// [workspace.dependencies] entry is listed under the member "workspace"
+    /// Requirements the prerelease policy or the MSRV rule out are not listed, their
+    /// members still move onto the result unless that would be a breaking change
+    /// A result that does not parse as a version requirement is reported as a conflict
+    fn resolve(&self, key: &DependencyKey, versions: &HashMap<String, Vec<String>>) -> Resolution;
+}
+
+/// Report why a built-in strategy could not resolve a conflict
+fn explain(result: Result<String>) -> Resolution {
+    match result {
+        Ok(version) => Resolution::Resolved(version),
+        Err(e) => Resolution::Conflict(Some(e.to_string())),
+    }
+}
+
+/// Leave every conflict unresolved, for the Skip and Fail strategies
+pub(crate) struct Unresolved(&'static str);
+
+impl VersionResolver for Unresolved {
+    fn name(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn resolve(
+        &self,
+        _key: &DependencyKey,
+        _versions: &HashMap<String, Vec<String>>,
+    ) -> Resolution {
+        Resolution::Conflict(None)
+    }
+}
+
+/// Use the requirement with the highest minimum version
+pub(crate) struct Highest;
 
-    if candidates.is_empty() {
-        anyhow::bail!("No valid semver versions found");
+impl VersionResolver for Highest {
+    fn name(&self) -> String {
+        "Highest".to_string()
     }
 
+    fn resolve(&self, _key: &DependencyKey, versions: &HashMap<String, Vec<String>>) -> Resolution {
+        explain(resolve_by_order(versions, true))
+    }
+}
+
+/// Use the requirement with the lowest minimum version
+pub(crate) struct Lowest;
+
+impl VersionResolver for Lowest {
+    fn name(&self) -> String {
+        "Lowest".to_string()
+    }
+
+    fn resolve(&self, _key: &DependencyKey, versions: &HashMap<String, Vec<String>>) -> Resolution {
+        explain(resolve_by_order(versions, false))
+    }
+}
+
+/// Use the highest minimum version that satisfies every requirement
+pub(crate) struct HighestCompatible;
+
+impl VersionResolver for HighestCompatible {
+    fn name(&self) -> String {
+        "HighestCompatible".to_string()
+    }
+
+    fn resolve(&self, _key: &DependencyKey, versions: &HashMap<String, Vec<String>>) -> Resolution {
+        explain(resolve_highest_compatible(versions))
+    }
+}
+
+/// Write the intersection of every requirement
+pub(crate) struct Intersect;
+
+impl VersionResolver for Intersect {
+    fn name(&self) -> String {
+        "Intersect".to_string()
+    }
+
+    fn resolve(&self, _key: &DependencyKey, versions: &HashMap<String, Vec<String>>) -> Resolution {
+        explain(resolve_intersect(versions))
+    }
+}
+
+/// Use the requirement most members write
+pub(crate) struct Majority;
+
+impl VersionResolver for Majority {
+    fn name(&self) -> String {
+        "Majority".to_string()
+    }
+
+    fn resolve(&self, _key: &DependencyKey, versions: &HashMap<String, Vec<String>>) -> Resolution {
+        explain(resolve_majority(versions))
+    }
+}
+
+/// Use the version Cargo.lock resolves to, falling back to highest-compatible
+pub(crate) struct Locked<'a> {
+    /// Versions of the crate recorded in Cargo.lock
+    pub(crate) locked: &'a [Version],
+}
+
+impl VersionResolver for Locked<'_> {
+    fn name(&self) -> String {
+        "Lockfile".to_string()
+    }
+
+    fn resolve(&self, _key: &DependencyKey, versions: &HashMap<String, Vec<String>>) -> Resolution {
+        explain(
+            resolve_locked(versions, self.locked).or_else(|_| resolve_highest_compatible(versions)),
+        )
+    }
+}
+
+/// Use the newest published version satisfying every requirement, falling back to highest-compatible
+pub(crate) struct LatestCompatible<'a> {
+    /// Versions of the crate in the registry index
+    pub(crate) published: &'a [IndexEntry],
+}
+
+impl VersionResolver for LatestCompatible<'_> {
+    fn name(&self) -> String {
+        "LatestCompatible".to_string()
+    }
+
+    fn resolve(&self, _key: &DependencyKey, versions: &HashMap<String, Vec<String>>) -> Resolution {
+        explain(
+            resolve_latest_published(versions, self.published)
+                .or_else(|_| resolve_highest_compatible(versions)),
+        )
+    }
+}
+
+/// Resolver implementing a built-in strategy, with the lockfile and registry
+/// index data it needs for one dependency
+pub(crate) fn builtin_resolver<'a>(
+    strategy: VersionResolutionStrategy,
+    locked: &'a [Version],
+    published: &'a [IndexEntry],
+) -> Box<dyn VersionResolver + 'a> {
+    match strategy {
+        VersionResolutionStrategy::Skip => Box::new(Unresolved("Skip")),
+        VersionResolutionStrategy::Fail => Box::new(Unresolved("Fail")),
+        VersionResolutionStrategy::Highest => Box::new(Highest),
+        VersionResolutionStrategy::Lowest => Box::new(Lowest),
+        VersionResolutionStrategy::HighestCompatible => Box::new(HighestCompatible),
+        VersionResolutionStrategy::Intersect => Box::new(Intersect),
+        VersionResolutionStrategy::Majority => Box::new(Majority),
+        VersionResolutionStrategy::Lockfile => Box::new(Locked { locked }),
+        VersionResolutionStrategy::LatestCompatible => Box::new(LatestCompatible { published }),
+    }
+}
+
+/// Find and return the requirement with the highest (or lowest) minimum version
+fn resolve_by_order(version_map: &HashMap<String, Vec<String>>, take_last: bool) -> Result<String> {
+    let mut requirements = parse_requirements(version_map)?;
+
+    // Order by minimum version, then by how far the range reaches (unbounded last)
+    requirements.sort_by(|a, b| {
+        a.minimum()
+            .cmp(b.minimum())
+            .then_with(|| match (&a.range.upper, &b.range.upper) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (a, b) => a.is_none().cmp(&b.is_none()),
+            })
+    });
+    let requirement = if take_last {
+        requirements.last()
+    } else {
+        requirements.first()
+    }
+    .unwrap();
+    Ok(requirement.raw.to_string())
+}
+
+/// Use the version Cargo.lock resolves to, if it is the only locked version
+/// and satisfies all requirements
+fn resolve_locked(
+    version_map: &HashMap<String, Vec<String>>,
+    locked: &[Version],
+) -> Result<String> {
+    let [version] = locked else {
+        anyhow::bail!("Cargo.lock has {} versions", locked.len());
+    };
+
+    let requirements = parse_requirements(version_map)?;
+    anyhow::ensure!(
+        requirements.iter().all(|r| r.req.matches(version)),
+        "Locked version {} does not satisfy all requirements",
+        version
+    );
+
+    Ok(version.to_string())
+}
+
+/// Use the newest published, non-yanked version that satisfies all requirements,
+/// written so the workspace entry still satisfies them, e.g. `~1.0.210` if a member uses `~1.0`
+fn resolve_latest_published(
+    version_map: &HashMap<String, Vec<String>>,
+    published: &[IndexEntry],
+) -> Result<String> {
+    let requirements = parse_requirements(version_map)?;
+
+    let latest = published
+        .iter()
+        .filter(|entry| !entry.yanked)
+        .map(|entry| &entry.version)
+        .filter(|version| requirements.iter().all(|r| r.req.matches(version)))
+        .max()
+        .ok_or_else(|| anyhow::anyhow!("No published version satisfies all requirements"))?;
+
+    let range = VersionReq::parse(&latest.to_string())
+        .ok()
+        .and_then(|req| VersionRange::from_req(&req))
+        .and_then(|latest| {
+            requirements
+                .iter()
+                .try_fold(latest, |range, r| range.intersect(&r.range))
+        })
+        .ok_or_else(|| anyhow::anyhow!("{} does not satisfy all requirements", latest))?;
+
+    Ok(range.to_requirement(3))
+}
+
+/// Find the highest minimum version of any requirement that satisfies all requirements
+/// Caret requirement on `candidate`, or the intersection of all requirements when the
+/// caret form would accept versions one of them rules out, e.g. `~1.2` and `1.2.5`
+/// give `~1.2.5` rather than `1.2.5`
+fn bounded_requirement(
+    requirements: &[Requirement],
+    candidate: &Version,
+    precision: usize,
+) -> String {
+    let caret = format_version(candidate, precision);
+    let intersection = requirements
+        .iter()
+        .try_fold(VersionRange::ANY, |range, r| range.intersect(&r.range));
+    let caret_range = VersionReq::parse(&caret)
+        .ok()
+        .and_then(|req| VersionRange::from_req(&req));
+
+    match (intersection, caret_range) {
+        (Some(range), Some(caret_range))
+            if range.intersect(&caret_range).as_ref() != Some(&caret_range) =>
+        {
+            range.to_requirement(precision)
+        }
+        _ => caret,
+    }
+}
+
+fn resolve_highest_compatible(version_map: &HashMap<String, Vec<String>>) -> Result<String> {
+    let requirements = parse_requirements(version_map)?;
+
+    // Highest minimum first, written with the most precise form among equal minimums
+    let mut candidates: Vec<(&Version, usize)> = requirements
+        .iter()
+        .map(|r| (r.minimum(), r.precision()))
+        .collect();
     candidates.sort();
     candidates.reverse();
 
-    for candidate in &candidates {
-        if reqs.iter().all(|req| req.matches(candidate)) {
-            return Ok((candidate.to_string(), members));
+    for (candidate, precision) in candidates {
+        if requirements.iter().all(|r| r.req.matches(candidate)) {
+            return Ok(bounded_requirement(&requirements, candidate, precision));
         }
     }
 
-    anyhow::bail!("No version satisfies all requirements")
+    let pairs = incompatible_pairs(version_map);
+    if pairs.is_empty() {
+        anyhow::bail!("No version satisfies all requirements")
+    }
+    anyhow::bail!(
+        "No version satisfies all requirements: {}",
+        pairs.join("; ")
+    )
+}
+
+/// Pick the requirement used by the most members, the workspace entry counting as one vote
+/// Ties are broken by resolving the tied requirements with highest-compatible
+fn resolve_majority(version_map: &HashMap<String, Vec<String>>) -> Result<String> {
+    // A member using the crate in several sections still votes once
+    let votes = |users: &[String]| users.iter().collect::<HashSet<_>>().len();
+    let most = version_map
+        .values()
+        .map(|users| votes(users))
+        .max()
+        .unwrap_or_default();
+
+    let tied: HashMap<String, Vec<String>> = version_map
+        .iter()
+        .filter(|(_, users)| votes(users) == most)
+        .map(|(raw, users)| (raw.clone(), users.clone()))
+        .collect();
+
+    match tied.keys().collect::<Vec<_>>().as_slice() {
+        [raw] => {
+            // Still reject requirements that are not valid semver
+            parse_requirements(version_map)?;
+            Ok(raw.to_string())
+        }
+        _ => resolve_highest_compatible(&tied),
+    }
+}
+
+/// Write the intersection of all requirements as a single requirement
+fn resolve_intersect(version_map: &HashMap<String, Vec<String>>) -> Result<String> {
+    let requirements = parse_requirements(version_map)?;
+
+    let intersection = requirements
+        .iter()
+        .try_fold(VersionRange::ANY, |range, r| range.intersect(&r.range));
+
+    match intersection {
+        Some(range) => {
+            // Keep the precision of the requirement the lower bound comes from
+            let precision = requirements
+                .iter()
+                .filter(|r| r.range.lower == range.lower)
+                .map(|r| r.precision())
+                .max()
+                .unwrap_or(3);
+            Ok(range.to_requirement(precision))
+        }
+        None => anyhow::bail!(
+            "No version satisfies all requirements: {}",
+            incompatible_pairs(version_map).join("; ")
+        ),
+    }
 }
 
 #[cfg(test)]
@@ -171,43 +862,61 @@ mod tests {
             .collect()
     }
 
+    /// Resolve with a built-in strategy, the note explaining a conflict as the error
+    fn resolve(
+        version_map: &HashMap<String, Vec<String>>,
+        strategy: VersionResolutionStrategy,
+        locked: &[Version],
+        published: &[IndexEntry],
+    ) -> Result<String, String> {
+        let key = DependencyKey {
+            name: "dep".to_string(),
+            package: None,
+            registry: None,
+        };
+        match builtin_resolver(strategy, locked, published).resolve(&key, version_map) {
+            Resolution::Resolved(version) => Ok(version),
+            Resolution::Conflict(note) => Err(note.unwrap_or_default()),
+        }
+    }
+
     // Tests for Highest strategy
     #[rstest]
     #[case::minor_versions(&[("1.0.0", vec!["m1"]), ("1.1.0", vec!["m2"])], "1.1.0")]
-    #[case::two_component_versions(&[("0.69", vec!["m1"]), ("0.70", vec!["m2"])], "0.70.0")]
+    #[case::two_component_versions(&[("0.69", vec!["m1"]), ("0.70", vec!["m2"])], "0.70")]
     #[case::patch_versions(&[("1.0.100", vec!["m1"]), ("1.0.150", vec!["m2"]), ("1.0.120", vec!["m3"])], "1.0.150")]
-    #[case::single_component_versions(&[("2", vec!["m1"]), ("3", vec!["m2"])], "3.0.0")]
+    #[case::single_component_versions(&[("2", vec!["m1"]), ("3", vec!["m2"])], "3")]
     #[case::mixed_versions(&[("0.1.0", vec!["m1"]), ("0.2.0", vec!["m2"]), ("0.1.5", vec!["m3"])], "0.2.0")]
     fn test_highest_strategy(#[case] versions: &[(&str, Vec<&str>)], #[case] expected: &str) {
         let version_map = make_version_map(versions);
-        let result = resolve_version_conflict(&version_map, &VersionResolutionStrategy::Highest);
+        let result = resolve(&version_map, VersionResolutionStrategy::Highest, &[], &[]);
 
-        let (version, _) = result.unwrap();
+        let version = result.unwrap();
         assert_eq!(version, expected);
     }
 
     // Tests for Lowest strategy
     #[rstest]
     #[case::minor_versions(&[("1.0.0", vec!["m1"]), ("1.1.0", vec!["m2"])], "1.0.0")]
-    #[case::two_component_versions(&[("0.69", vec!["m1"]), ("0.70", vec!["m2"])], "0.69.0")]
+    #[case::two_component_versions(&[("0.69", vec!["m1"]), ("0.70", vec!["m2"])], "0.69")]
     #[case::patch_versions(&[("1.0.100", vec!["m1"]), ("1.0.150", vec!["m2"]), ("1.0.120", vec!["m3"])], "1.0.100")]
-    #[case::single_component_versions(&[("2", vec!["m1"]), ("3", vec!["m2"])], "2.0.0")]
+    #[case::single_component_versions(&[("2", vec!["m1"]), ("3", vec!["m2"])], "2")]
     fn test_lowest_strategy(#[case] versions: &[(&str, Vec<&str>)], #[case] expected: &str) {
         let version_map = make_version_map(versions);
-        let result = resolve_version_conflict(&version_map, &VersionResolutionStrategy::Lowest);
+        let result = resolve(&version_map, VersionResolutionStrategy::Lowest, &[], &[]);
 
-        let (version, _) = result.unwrap();
+        let version = result.unwrap();
         assert_eq!(version, expected);
     }
 
     // Tests for HighestCompatible strategy
     #[rstest]
     #[case::compatible_patch_versions(&[("1.0.100", vec!["m1"]), ("1.0.150", vec!["m2"]), ("1.0.120", vec!["m3"])], true, "1.0.150", "")]
-    #[case::compatible_minor_versions(&[("1.0", vec!["m1"]), ("1.2", vec!["m2"])], true, "1.2.0", "")]
-    #[case::incompatible_major_versions(&[("1.0.0", vec!["m1"]), ("2.0.0", vec!["m2"])], false, "", "No version satisfies all requirements")]
-    #[case::incompatible_in_zero_x(&[("0.1.0", vec!["m1"]), ("0.2.0", vec!["m2"])], false, "", "No version satisfies all requirements")]
+    #[case::compatible_minor_versions(&[("1.0", vec!["m1"]), ("1.2", vec!["m2"])], true, "1.2", "")]
+    #[case::incompatible_major_versions(&[("1.0.0", vec!["m1"]), ("2.0.0", vec!["m2"])], false, "", "No version satisfies all requirements: '1.0.0' (m1) and '2.0.0' (m2) do not overlap")]
+    #[case::incompatible_in_zero_x(&[("0.1.0", vec!["m1"]), ("0.2.0", vec!["m2"])], false, "", "No version satisfies all requirements: '0.1.0' (m1) and '0.2.0' (m2) do not overlap")]
     #[case::mixed_compatible(&[("1.5.0", vec!["m1"]), ("1.6.0", vec!["m2"]), ("1.5.5", vec!["m3"])], true, "1.6.0", "")]
-    #[case::zero_zero_x_incompatible(&[("0.0.1", vec!["m1"]), ("0.0.2", vec!["m2"])], false, "", "No version satisfies all requirements")]
+    #[case::zero_zero_x_incompatible(&[("0.0.1", vec!["m1"]), ("0.0.2", vec!["m2"])], false, "", "No version satisfies all requirements: '0.0.1' (m1) and '0.0.2' (m2) do not overlap")]
     #[case::compatible_with_two_component(&[("1.5", vec!["m1"]), ("1.6.0", vec!["m2"])], true, "1.6.0", "")]
     #[case::single_to_three_component(&[("2", vec!["m1"]), ("2.1.0", vec!["m2"])], true, "2.1.0", "")]
     fn test_highest_compatible_strategy(
@@ -217,36 +926,335 @@ mod tests {
         #[case] expected_err: &str,
     ) {
         let version_map = make_version_map(versions);
-        let result =
-            resolve_version_conflict(&version_map, &VersionResolutionStrategy::HighestCompatible);
+        let result = resolve(
+            &version_map,
+            VersionResolutionStrategy::HighestCompatible,
+            &[],
+            &[],
+        );
 
         if should_succeed {
-            let (version, _) = result.unwrap();
+            let version = result.unwrap();
             assert_eq!(version, expected);
         } else {
             let err = result.unwrap_err();
-            assert_eq!(err.to_string(), expected_err);
+            assert_eq!(err, expected_err);
         }
     }
 
-    // Tests for Skip and Fail strategies
+    // Tests for the range accepted by each Cargo requirement form
     #[rstest]
-    #[case::skip_with_conflict(VersionResolutionStrategy::Skip, "Skip strategy")]
-    #[case::fail_with_conflict(
-        VersionResolutionStrategy::Fail,
-        "Version conflict detected with fail strategy"
-    )]
-    fn test_no_resolution_strategies(
+    #[case::bare("1.2.3", "1.2.3", Some("2.0.0"))]
+    #[case::bare_minor("0.69", "0.69.0", Some("0.70.0"))]
+    #[case::bare_zero_zero("0.0.3", "0.0.3", Some("0.0.4"))]
+    #[case::caret("^1.2", "1.2.0", Some("2.0.0"))]
+    #[case::tilde("~1.2", "1.2.0", Some("1.3.0"))]
+    #[case::tilde_major("~1", "1.0.0", Some("2.0.0"))]
+    #[case::exact("=1.0.3", "1.0.3", Some("1.0.4"))]
+    #[case::exact_partial("=1.2", "1.2.0", Some("1.3.0"))]
+    #[case::bounded(">=1, <2", "1.0.0", Some("2.0.0"))]
+    #[case::bounded_minor(">=1.2, <1.5", "1.2.0", Some("1.5.0"))]
+    #[case::greater(">1.2.3", "1.2.4", None)]
+    #[case::less_eq("<=1.4", "0.0.0", Some("1.5.0"))]
+    #[case::wildcard_major("1.*", "1.0.0", Some("2.0.0"))]
+    #[case::wildcard_minor("1.2.*", "1.2.0", Some("1.3.0"))]
+    #[case::any("*", "0.0.0", None)]
+    fn test_requirement_range(#[case] raw: &str, #[case] lower: &str, #[case] upper: Option<&str>) {
+        let requirement = Requirement::parse(raw, &[]).unwrap();
+        assert_eq!(
+            requirement.range,
+            VersionRange {
+                lower: Version::parse(lower).unwrap(),
+                upper: upper.map(|u| Version::parse(u).unwrap()),
+            }
+        );
+    }
+
+    // Tests for strategies with non-caret requirements
+    #[rstest]
+    #[case::highest_with_tilde(VersionResolutionStrategy::Highest, &[("~1.2", vec!["m1"]), ("1.1", vec!["m2"])], "~1.2")]
+    #[case::highest_with_any(VersionResolutionStrategy::Highest, &[("*", vec!["m1"]), ("1.1", vec!["m2"])], "1.1")]
+    #[case::lowest_with_any(VersionResolutionStrategy::Lowest, &[("*", vec!["m1"]), ("1.1", vec!["m2"])], "*")]
+    #[case::lowest_with_exact(VersionResolutionStrategy::Lowest, &[("=1.0.3", vec!["m1"]), ("1.1", vec!["m2"])], "=1.0.3")]
+    #[case::compatible_with_range(VersionResolutionStrategy::HighestCompatible, &[(">=1.2, <1.5", vec!["m1"]), ("1.4.1", vec!["m2"])], "~1.4.1")]
+    #[case::compatible_with_wildcard(VersionResolutionStrategy::HighestCompatible, &[("1.*", vec!["m1"]), ("1.4", vec!["m2"])], "1.4")]
+    #[case::compatible_with_any(VersionResolutionStrategy::HighestCompatible, &[("*", vec!["m1"]), ("0.3", vec!["m2"])], "0.3")]
+    #[case::compatible_with_exact(VersionResolutionStrategy::HighestCompatible, &[("=1.0.3", vec!["m1"]), ("1.0", vec!["m2"])], "=1.0.3")]
+    #[case::compatible_with_tilde(VersionResolutionStrategy::HighestCompatible, &[("~1.2", vec!["m1"]), ("1.2.5", vec!["m2"])], "~1.2.5")]
+    #[case::compatible_with_upper_bound(VersionResolutionStrategy::HighestCompatible, &[(">=0.4, <0.4.10", vec!["m1"]), ("0.4.5", vec!["m2"])], ">=0.4.5, <0.4.10")]
+    fn test_requirement_forms(
         #[case] strategy: VersionResolutionStrategy,
-        #[case] expected_err: &str,
+        #[case] versions: &[(&str, Vec<&str>)],
+        #[case] expected: &str,
+    ) {
+        let version_map = make_version_map(versions);
+        let version = resolve(&version_map, strategy, &[], &[]).unwrap();
+        assert_eq!(version, expected);
+    }
+
+    #[test]
+    fn test_incompatible_requirement_diagnostics() {
+        let version_map = make_version_map(&[
+            ("~1.2", vec!["m1"]),
+            ("1.4", vec!["m2", "m3"]),
+            (">=1.2, <1.5", vec!["m4"]),
+        ]);
+        let err = resolve(
+            &version_map,
+            VersionResolutionStrategy::HighestCompatible,
+            &[],
+            &[],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "No version satisfies all requirements: '1.4' (m2, m3) and '~1.2' (m1) do not overlap"
+        );
+    }
+
+    // Tests for Lockfile strategy
+    #[rstest]
+    #[case::single_locked_version(&[("1.0", vec!["m1"]), ("1.0.150", vec!["m2"])], &["1.0.197"], "1.0.197")]
+    #[case::not_in_lockfile(&[("1.0", vec!["m1"]), ("1.2", vec!["m2"])], &[], "1.2")]
+    #[case::several_locked_versions(&[("1.0", vec!["m1"]), ("1.2", vec!["m2"])], &["0.9.0", "1.2.5"], "1.2")]
+    #[case::locked_below_requirement(&[("1.0", vec!["m1"]), ("1.2", vec!["m2"])], &["1.1.0"], "1.2")]
+    fn test_lockfile_strategy(
+        #[case] versions: &[(&str, Vec<&str>)],
+        #[case] locked: &[&str],
+        #[case] expected: &str,
+    ) {
+        let version_map = make_version_map(versions);
+        let locked: Vec<Version> = locked.iter().map(|v| Version::parse(v).unwrap()).collect();
+        let result = resolve(
+            &version_map,
+            VersionResolutionStrategy::Lockfile,
+            &locked,
+            &[],
+        );
+
+        let version = result.unwrap();
+        assert_eq!(version, expected);
+    }
+
+    // Tests for LatestCompatible strategy
+    #[rstest]
+    #[case::newest_compatible(&[("1.0", vec!["m1"]), ("1.0.150", vec!["m2"])], &["1.0.150", "1.0.210", "2.0.0"], "1.0.210")]
+    #[case::narrowed_by_tilde(&[("1.0", vec!["m1"]), ("~1.0.150", vec!["m2"])], &["1.0.210", "1.1.0"], "~1.0.210")]
+    #[case::yanked_skipped(&[("1.0", vec!["m1"]), ("1.0.150", vec!["m2"])], &["1.0.150", "1.0.210!"], "1.0.150")]
+    #[case::not_in_index(&[("1.0", vec!["m1"]), ("1.2", vec!["m2"])], &[], "1.2")]
+    #[case::none_compatible(&[("1.0", vec!["m1"]), ("1.2", vec!["m2"])], &["1.1.0"], "1.2")]
+    fn test_latest_compatible_strategy(
+        #[case] versions: &[(&str, Vec<&str>)],
+        #[case] published: &[&str],
+        #[case] expected: &str,
+    ) {
+        let version_map = make_version_map(versions);
+        // A trailing `!` marks a yanked version
+        let published: Vec<IndexEntry> = published
+            .iter()
+            .map(|v| IndexEntry {
+                version: Version::parse(v.trim_end_matches('!')).unwrap(),
+                rust_version: None,
+                yanked: v.ends_with('!'),
+            })
+            .collect();
+        let result = resolve(
+            &version_map,
+            VersionResolutionStrategy::LatestCompatible,
+            &[],
+            &published,
+        );
+
+        let version = result.unwrap();
+        assert_eq!(version, expected);
+    }
+
+    // Tests for Intersect strategy
+    #[rstest]
+    #[case::range_and_caret(&[(">=1.2, <1.5", vec!["m1"]), ("1.4.1", vec!["m2"])], "~1.4.1")]
+    #[case::caret_versions(&[("1.0", vec!["m1"]), ("1.2", vec!["m2"])], "1.2")]
+    #[case::zero_major(&[("0.3", vec!["m1"]), ("0.3.4", vec!["m2"])], "0.3.4")]
+    #[case::tilde_and_caret(&[("~1.2", vec!["m1"]), ("1.2.3", vec!["m2"])], "~1.2.3")]
+    #[case::exact_pin(&[("=1.0.3", vec!["m1"]), ("1.0", vec!["m2"])], "=1.0.3")]
+    #[case::any_and_caret(&[("*", vec!["m1"]), ("0.4.20", vec!["m2"])], "0.4.20")]
+    #[case::open_ranges(&[(">=1.2", vec!["m1"]), (">=1.5", vec!["m2"])], ">=1.5")]
+    #[case::upper_only(&[("<1.5", vec!["m1"]), ("<=1.2.3", vec!["m2"])], "<1.2.4")]
+    #[case::bounded_range(&[(">=1.2, <3", vec!["m1"]), (">=1.5, <2.5", vec!["m2"])], ">=1.5, <2.5")]
+    fn test_intersect_strategy(#[case] versions: &[(&str, Vec<&str>)], #[case] expected: &str) {
+        let version_map = make_version_map(versions);
+        let version =
+            resolve(&version_map, VersionResolutionStrategy::Intersect, &[], &[]).unwrap();
+        assert_eq!(version, expected);
+
+        // The lowest version accepted by the written requirement satisfies every member
+        let lower = Requirement::parse(&version, &[]).unwrap().range.lower;
+        for raw in version_map.keys() {
+            assert!(VersionReq::parse(raw).unwrap().matches(&lower));
+        }
+    }
+
+    #[test]
+    fn test_intersect_incompatible() {
+        let version_map = make_version_map(&[
+            ("~1.2", vec!["m1"]),
+            ("1.4", vec!["m2"]),
+            (">=1.2.5", vec!["m3"]),
+        ]);
+        let err =
+            resolve(&version_map, VersionResolutionStrategy::Intersect, &[], &[]).unwrap_err();
+        assert_eq!(
+            err,
+            "No version satisfies all requirements: '1.4' (m2) and '~1.2' (m1) do not overlap"
+        );
+    }
+
+    // Tests for Majority strategy
+    #[rstest]
+    #[case::most_members(&[("1.0.75", vec!["m1", "m2"]), ("1.0.80", vec!["m3"])], "1.0.75")]
+    #[case::workspace_vote(&[("1.0.100", vec!["m1", "workspace"]), ("1.0.150", vec!["m2"])], "1.0.100")]
+    #[case::tie_highest_compatible(&[("0.4.17", vec!["m1"]), ("0.4.20", vec!["m2"])], "0.4.20")]
+    #[case::member_in_several_sections(&[("1.0", vec!["m1", "m1"]), ("1.2", vec!["m2"])], "1.2")]
+    #[case::incompatible_majority(&[("1", vec!["m1", "m2"]), ("2", vec!["m3"])], "1")]
+    fn test_majority_strategy(#[case] versions: &[(&str, Vec<&str>)], #[case] expected: &str) {
+        let version_map = make_version_map(versions);
+        let version = resolve(&version_map, VersionResolutionStrategy::Majority, &[], &[]).unwrap();
+        assert_eq!(version, expected);
+    }
+
+    #[test]
+    fn test_majority_incompatible_tie() {
+        let version_map = make_version_map(&[("1", vec!["m1"]), ("2", vec!["m2"])]);
+        let err = resolve(&version_map, VersionResolutionStrategy::Majority, &[], &[]).unwrap_err();
+        assert_eq!(
+            err,
+            "No version satisfies all requirements: '1' (m1) and '2' (m2) do not overlap"
+        );
+    }
+
+    #[rstest]
+    #[case::exact("=1.38.0", true)]
+    #[case::exact_prerelease("=1.0.0-rc.1", true)]
+    #[case::partial_exact("=1.38", false)]
+    #[case::caret("1.38.0", false)]
+    #[case::range(">=1.38.0, <1.39", false)]
+    fn test_is_exact_pin(#[case] requirement: &str, #[case] expected: bool) {
+        assert_eq!(is_exact_pin(requirement), expected);
+    }
+
+    #[rstest]
+    #[case::unchanged("1.0", "1.0.0", Some(VersionChange::Unchanged))]
+    #[case::upgrade("1.0.100", "1.0.150", Some(VersionChange::Upgrade))]
+    #[case::downgrade("1.0.150", "1.0.100", Some(VersionChange::Downgrade))]
+    #[case::breaking_major("1.0", "2.0", Some(VersionChange::Breaking))]
+    #[case::breaking_zero_minor("0.7", "0.8", Some(VersionChange::Breaking))]
+    #[case::breaking_downgrade("2.0", "1.0", Some(VersionChange::Breaking))]
+    #[case::wildcard_accepts("*", "0.4.20", Some(VersionChange::Upgrade))]
+    #[case::range_accepts(">=1.0, <3", "2.1", Some(VersionChange::Upgrade))]
+    #[case::invalid("invalid", "1.0", None)]
+    fn test_classify_change(
+        #[case] original: &str,
+        #[case] resolved: &str,
+        #[case] expected: Option<VersionChange>,
     ) {
+        assert_eq!(classify_change(original, resolved), expected);
+    }
+
+    // Tests for partial consolidation
+    #[rstest]
+    #[case::outlier_major(&[("1.0", vec!["m1", "m2"]), ("1.0.100", vec!["m3"]), ("0.9", vec!["m4"])], &["1.0", "1.0.100"])]
+    #[case::zero_minor_groups(&[("0.3", vec!["m1"]), ("0.4", vec!["m2", "m3"]), ("0.4.1", vec!["m2"])], &["0.4", "0.4.1"])]
+    #[case::members_counted_once(&[("0.3", vec!["m1", "m2"]), ("0.4", vec!["m3"]), ("0.4.2", vec!["m3"])], &["0.3"])]
+    #[case::workspace_counts(&[("1", vec!["m1"]), ("2", vec!["m2", "workspace"])], &["2"])]
+    #[case::tie_prefers_newest(&[("1.0", vec!["m1"]), ("2.0", vec!["m2"])], &["2.0"])]
+    #[case::invalid_keeps_all(&[("1.0", vec!["m1"]), ("invalid", vec!["m2"])], &["1.0", "invalid"])]
+    fn test_largest_compatible_group(
+        #[case] versions: &[(&str, Vec<&str>)],
+        #[case] expected: &[&str],
+    ) {
+        let version_map = make_version_map(versions);
+        let mut group: Vec<_> = largest_compatible_group(&version_map).into_iter().collect();
+        group.sort();
+        assert_eq!(group, expected);
+    }
+
+    // Tests for prerelease policy
+    #[rstest]
+    #[case::allow(PrereleasePolicy::Allow, &[("1.0.0", vec!["m1"]), ("1.1.0-alpha", vec!["m2"])], &["1.0.0", "1.1.0-alpha"])]
+    #[case::prefer_stable(PrereleasePolicy::PreferStable, &[("1.0.0", vec!["m1"]), ("1.1.0-alpha", vec!["m2"])], &["1.0.0"])]
+    #[case::prefer_stable_only_prereleases(PrereleasePolicy::PreferStable, &[("2.0.0-rc.1", vec!["m1"]), ("2.0.0-rc.2", vec!["m2"])], &["2.0.0-rc.1", "2.0.0-rc.2"])]
+    #[case::deny(PrereleasePolicy::Deny, &[(">=1.0.0-rc.1, <2", vec!["m1"]), ("1.2", vec!["m2"])], &["1.2"])]
+    fn test_prerelease_policy(
+        #[case] policy: PrereleasePolicy,
+        #[case] versions: &[(&str, Vec<&str>)],
+        #[case] expected: &[&str],
+    ) {
+        let version_map = make_version_map(versions);
+        let mut candidates: Vec<_> = apply_prerelease_policy(&version_map, &policy)
+            .into_keys()
+            .collect();
+        candidates.sort();
+        assert_eq!(candidates, expected);
+    }
+
+    // Tests for rust-version filtering
+    #[rstest]
+    #[case::oldest_match_decides("1.0.100", "1.60", &["1.0.100"])]
+    #[case::newer_requirement_rejected("1.0.200", "1.60", &["1.0.100"])]
+    #[case::newer_rust_accepts_all("1.0.200", "1.61", &["1.0.100", "1.0.200"])]
+    #[case::yanked_skipped("1.0.150", "1.55", &["1.0.100"])]
+    #[case::unknown_kept("1.1", "1.40", &["1.0.100", "1.1"])]
+    fn test_apply_msrv(#[case] requirement: &str, #[case] msrv: &str, #[case] expected: &[&str]) {
+        let entry = |version: &str, rust_version: Option<&str>, yanked: bool| IndexEntry {
+            version: Version::parse(version).unwrap(),
+            rust_version: rust_version.and_then(parse_version_lenient),
+            yanked,
+        };
+        let published = [
+            entry("1.0.100", None, false),
+            entry("1.0.150", None, true),
+            entry("1.0.160", Some("1.56"), false),
+            entry("1.0.200", Some("1.61"), false),
+        ];
+        let version_map = make_version_map(&[("1.0.100", vec!["m1"]), (requirement, vec!["m2"])]);
+        let msrv = parse_version_lenient(msrv).unwrap();
+        let mut candidates: Vec<_> = apply_msrv(&version_map, &published, &msrv)
+            .into_keys()
+            .collect();
+        candidates.sort();
+        assert_eq!(candidates, expected);
+    }
+
+    // Tests for version format policy
+    #[rstest]
+    #[case::as_written(VersionFormat::AsWritten, "1.2.3", "1.2.3")]
+    #[case::major(VersionFormat::Major, "1.2.3", "1")]
+    #[case::major_minor(VersionFormat::MajorMinor, "1.2.3", "1.2")]
+    #[case::full(VersionFormat::Full, "1.2", "1.2.0")]
+    #[case::full_caret(VersionFormat::Full, "^1", "^1.0.0")]
+    #[case::major_keeps_zero_minor(VersionFormat::Major, "0.3.4", "0.3")]
+    #[case::major_keeps_zero_patch(VersionFormat::MajorMinor, "0.0.5", "0.0.5")]
+    #[case::tilde_unchanged(VersionFormat::Major, "~1.2.3", "~1.2.3")]
+    #[case::range_unchanged(VersionFormat::Full, ">=1.2, <2", ">=1.2, <2")]
+    #[case::prerelease_unchanged(VersionFormat::Major, "1.0.0-rc.1", "1.0.0-rc.1")]
+    fn test_format_requirement(
+        #[case] format: VersionFormat,
+        #[case] requirement: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(format_requirement(requirement, &format), expected);
+    }
+
+    // Tests for Skip and Fail strategies
+    #[rstest]
+    #[case::skip_with_conflict(VersionResolutionStrategy::Skip)]
+    #[case::fail_with_conflict(VersionResolutionStrategy::Fail)]
+    fn test_no_resolution_strategies(#[case] strategy: VersionResolutionStrategy) {
         let version_map = make_version_map(&[("1.0.0", vec!["m1"]), ("1.1.0", vec!["m2"])]);
-        let result = resolve_version_conflict(&version_map, &strategy);
-        let err = result.unwrap_err();
-        assert_eq!(err.to_string(), expected_err);
+        let result = resolve(&version_map, strategy, &[], &[]);
+        assert_eq!(result, Err(String::new()));
     }
 
-    // Test that all strategies handle members correctly
+    // Test that requirements shared by several members resolve like any other
     #[rstest]
     #[case::highest(VersionResolutionStrategy::Highest, "1.5.0")]
     #[case::lowest(VersionResolutionStrategy::Lowest, "1.0.0")]
@@ -259,11 +1267,19 @@ mod tests {
             ("1.0.0", vec!["member1", "member2"]),
             ("1.5.0", vec!["member3"]),
         ]);
-        let result = resolve_version_conflict(&version_map, &strategy);
-        let (version, mut members) = result.unwrap();
-        assert_eq!(version, expected_version);
-        members.sort();
-        assert_eq!(members, vec!["member1", "member2", "member3"]);
+        let result = resolve(&version_map, strategy, &[], &[]);
+        assert_eq!(result.unwrap(), expected_version);
+    }
+
+    // The report names built-in resolvers after their strategy
+    #[test]
+    fn test_builtin_resolver_names() {
+        for strategy in <VersionResolutionStrategy as clap::ValueEnum>::value_variants() {
+            assert_eq!(
+                builtin_resolver(*strategy, &[], &[]).name(),
+                format!("{:?}", strategy)
+            );
+        }
     }
 
     // Test edge cases for all resolution strategies
@@ -273,32 +1289,38 @@ mod tests {
     #[case::highest_compatible_empty_map(VersionResolutionStrategy::HighestCompatible)]
     fn test_empty_version_map_fails(#[case] strategy: VersionResolutionStrategy) {
         let version_map: HashMap<String, Vec<String>> = HashMap::new();
-        let result = resolve_version_conflict(&version_map, &strategy);
+        let result = resolve(&version_map, strategy, &[], &[]);
         let err = result.unwrap_err();
-        assert_eq!(err.to_string(), "No valid semver versions found");
+        assert_eq!(err, "No valid semver versions found");
     }
 
     // Test with only invalid versions
     #[rstest]
-    #[case::highest_invalid(VersionResolutionStrategy::Highest, "No valid semver versions found")]
-    #[case::lowest_invalid(VersionResolutionStrategy::Lowest, "No valid semver versions found")]
+    #[case::highest_invalid(
+        VersionResolutionStrategy::Highest,
+        "Invalid requirement 'invalid1' in m1:"
+    )]
+    #[case::lowest_invalid(
+        VersionResolutionStrategy::Lowest,
+        "Invalid requirement 'invalid1' in m1:"
+    )]
     #[case::highest_compatible_invalid(
         VersionResolutionStrategy::HighestCompatible,
-        "Invalid version:"
+        "Invalid requirement 'invalid1' in m1:"
     )]
     fn test_invalid_versions_fail(
         #[case] strategy: VersionResolutionStrategy,
         #[case] expected_err: &str,
     ) {
         let version_map = make_version_map(&[("invalid1", vec!["m1"]), ("invalid2", vec!["m2"])]);
-        let result = resolve_version_conflict(&version_map, &strategy);
+        let result = resolve(&version_map, strategy, &[], &[]);
         let err = result.unwrap_err();
-        assert!(err.to_string().starts_with(expected_err));
+        assert!(err.starts_with(expected_err));
     }
 
     // Test edge cases specific to HighestCompatible
     #[rstest]
-    #[case::zero_zero_x_incompatible(&[("0.0.1", vec!["m1"]), ("0.0.2", vec!["m2"])], false, "", "No version satisfies all requirements")]
+    #[case::zero_zero_x_incompatible(&[("0.0.1", vec!["m1"]), ("0.0.2", vec!["m2"])], false, "", "No version satisfies all requirements: '0.0.1' (m1) and '0.0.2' (m2) do not overlap")]
     #[case::prerelease_versions(&[("1.0.0-alpha", vec!["m1"]), ("1.0.0-beta", vec!["m2"])], true, "1.0.0-beta", "")]
     #[case::zero_x_patch_compatible(&[("0.1.0", vec!["m1"]), ("0.1.5", vec!["m2"])], true, "0.1.5", "")]
     fn test_highest_compatible_edge_cases(
@@ -308,15 +1330,19 @@ mod tests {
         #[case] expected_err: &str,
     ) {
         let version_map = make_version_map(versions);
-        let result =
-            resolve_version_conflict(&version_map, &VersionResolutionStrategy::HighestCompatible);
+        let result = resolve(
+            &version_map,
+            VersionResolutionStrategy::HighestCompatible,
+            &[],
+            &[],
+        );
 
         if should_succeed {
-            let (version, _) = result.unwrap();
+            let version = result.unwrap();
             assert_eq!(version, expected);
         } else {
             let err = result.unwrap_err();
-            assert_eq!(err.to_string(), expected_err);
+            assert_eq!(err, expected_err);
         }
     }
 }
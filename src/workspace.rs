@@ -1,17 +1,27 @@
 use anyhow::{Context, Result};
 use cargo_metadata::MetadataCommand;
-use std::path::PathBuf;
+use semver::Version;
+use std::path::{Component, Path, PathBuf};
+
+use crate::manifest::read_manifest;
+use crate::version_resolver::parse_version_lenient;
 
 #[derive(Debug)]
 pub(crate) struct WorkspaceInfo {
     pub(crate) root_manifest: PathBuf,
     pub(crate) members: Vec<MemberInfo>,
+    /// Members left out by `filter_members_by_patterns`
+    pub(crate) excluded_members: Vec<MemberInfo>,
+    /// `rust-version` from [workspace.package]
+    pub(crate) rust_version: Option<Version>,
 }
 
 #[derive(Debug)]
 pub(crate) struct MemberInfo {
     pub(crate) name: String,
     pub(crate) manifest_path: PathBuf,
+    /// Declared (or inherited) `rust-version`
+    pub(crate) rust_version: Option<Version>,
 }
 
 /// Discover the workspace structure using the `cargo metadata` command
@@ -40,6 +50,7 @@ pub(crate) fn discover_workspace(
         .map(|pkg| MemberInfo {
             name: pkg.name.to_string(),
             manifest_path: pkg.manifest_path.clone().into_std_path_buf(),
+            rust_version: pkg.rust_version.clone(),
         })
         .collect();
 
@@ -47,12 +58,28 @@ pub(crate) fn discover_workspace(
         anyhow::bail!("No workspace members found. Is this a workspace?");
     }
 
+    let rust_version = workspace_rust_version(&root_manifest)?;
+
     Ok(WorkspaceInfo {
         root_manifest,
         members,
+        excluded_members: Vec::new(),
+        rust_version,
     })
 }
 
+/// Read `rust-version` from the [workspace.package] table of the root manifest
+fn workspace_rust_version(root_manifest: &Path) -> Result<Option<Version>> {
+    let doc = read_manifest(root_manifest)?;
+
+    Ok(doc
+        .get("workspace")
+        .and_then(|w| w.get("package"))
+        .and_then(|p| p.get("rust-version"))
+        .and_then(|v| v.as_str())
+        .and_then(parse_version_lenient))
+}
+
 impl WorkspaceInfo {
     /// Filter out workspace members matching pre-compiled glob patterns
     pub(crate) fn filter_members_by_patterns(&mut self, patterns: &[glob::Pattern]) -> usize {
@@ -60,11 +87,87 @@ impl WorkspaceInfo {
             return 0;
         }
 
-        let original_count = self.members.len();
+        let (excluded, members) = std::mem::take(&mut self.members)
+            .into_iter()
+            .partition(|member| patterns.iter().any(|pattern| pattern.matches(&member.name)));
+        self.members = members;
+        self.excluded_members = excluded;
 
-        self.members
-            .retain(|member| !patterns.iter().any(|pattern| pattern.matches(&member.name)));
+        self.excluded_members.len()
+    }
+
+    /// Workspace root directory
+    pub(crate) fn root_dir(&self) -> &Path {
+        self.root_manifest.parent().unwrap_or(Path::new("."))
+    }
 
-        original_count - self.members.len()
+    /// Rebase a `path = "..."` dependency declared in `manifest_path` onto the workspace root
+    /// Returns None if the path does not point at a workspace member, excluded or not
+    pub(crate) fn member_path_from(&self, manifest_path: &Path, path: &str) -> Option<String> {
+        let base = manifest_path.parent()?;
+        let target = normalize_path(&base.join(path));
+
+        let is_member = self
+            .members
+            .iter()
+            .chain(&self.excluded_members)
+            .any(|member| {
+                member
+                    .manifest_path
+                    .parent()
+                    .is_some_and(|dir| normalize_path(dir) == target)
+            });
+        if !is_member {
+            return None;
+        }
+
+        let relative = target.strip_prefix(normalize_path(self.root_dir())).ok()?;
+        let components: Vec<_> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect();
+        Some(components.join("/"))
+    }
+
+    /// Rebase a `path` from [workspace.dependencies] onto the directory of `manifest_path`,
+    /// the inverse of `member_path_from`
+    pub(crate) fn path_for_member(&self, manifest_path: &Path, path: &str) -> String {
+        let target = normalize_path(&self.root_dir().join(path));
+        let base = normalize_path(manifest_path.parent().unwrap_or(Path::new(".")));
+        let shared = target
+            .components()
+            .zip(base.components())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let components: Vec<_> =
+            std::iter::repeat_n("..".into(), base.components().count() - shared)
+                .chain(
+                    target
+                        .components()
+                        .skip(shared)
+                        .map(|c| c.as_os_str().to_string_lossy()),
+                )
+                .collect();
+        if components.is_empty() {
+            ".".to_string()
+        } else {
+            components.join("/")
+        }
+    }
+}
+
+/// Lexically resolve `.` and `..` components without touching the filesystem
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                result.pop();
+            }
+            other => result.push(other),
+        }
     }
+    result
 }
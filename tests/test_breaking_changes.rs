@@ -0,0 +1,70 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{Config, VersionResolutionStrategy};
+use test_helpers::TestWorkspace;
+
+#[test]
+fn reports_breaking_changes_per_member() -> Result<()> {
+    let workspace = TestWorkspace::new("test_breaking_changes/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolution_strategy: VersionResolutionStrategy::Highest,
+        ..Default::default()
+    })?;
+
+    let expected = r#"Found 3 members
+
+Summary:
+  2 dependencies to consolidate
+  2 version conflicts resolved
+
+Will consolidate:
+  rand = "0.8" in: member1, member2, member3
+  serde = "1.0.150" in: member1, member2, member3
+
+Resolved conflicts (using Highest):
+  rand: 0.7, 0.8 → 0.8 (1 member moves)
+    breaking: member1
+  serde: 1.0.100, 1.0.150 → 1.0.150 (1 member moves)
+    upgrade: member1
+
+Updating workspace Cargo.toml...
+Consolidated 2 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_breaking_changes/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn deny_breaking_refuses_to_apply() -> Result<()> {
+    let workspace = TestWorkspace::new("test_breaking_changes/before")?;
+
+    let err = workspace
+        .run(Config {
+            fix: true,
+            workspace_path: Some(workspace.path.clone()),
+            version_resolution_strategy: VersionResolutionStrategy::Highest,
+            deny_breaking_changes: true,
+            ..Default::default()
+        })
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Refusing to apply breaking version changes to: rand"
+    );
+
+    workspace.assert_matches("test_breaking_changes/before")?;
+
+    Ok(())
+}
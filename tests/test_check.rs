@@ -1,7 +1,7 @@
 mod test_helpers;
 
 use anyhow::Result;
-use cargo_workspace_deps::{Config, OutputFormat};
+use cargo_workspace_deps::Config;
 use test_helpers::TestWorkspace;
 
 #[test]
@@ -10,17 +10,10 @@ fn fails_when_consolidation_possible() -> Result<()> {
 
     let result = workspace.run(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
         check: true,
         version_resolution_strategy: cargo_workspace_deps::VersionResolutionStrategy::Skip,
-        output_format: OutputFormat::Text,
-        output_callback: None,
+        ..Default::default()
     });
 
     // Check mode should return Err when consolidation is possible
@@ -39,17 +32,10 @@ fn fails_when_conflicts_cannot_be_resolved() -> Result<()> {
 
     let result = workspace.run(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
         check: true,
         version_resolution_strategy: cargo_workspace_deps::VersionResolutionStrategy::Fail,
-        output_format: OutputFormat::Text,
-        output_callback: None,
+        ..Default::default()
     });
 
     let err = result.unwrap_err();
@@ -67,17 +53,10 @@ fn passes_when_no_consolidation_needed() -> Result<()> {
 
     let result = workspace.run(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
         check: true,
         version_resolution_strategy: cargo_workspace_deps::VersionResolutionStrategy::Skip,
-        output_format: OutputFormat::Text,
-        output_callback: None,
+        ..Default::default()
     });
 
     result.expect("should not fail");
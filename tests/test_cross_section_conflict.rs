@@ -1,7 +1,7 @@
 mod test_helpers;
 
 use anyhow::Result;
-use cargo_workspace_deps::{Config, OutputFormat};
+use cargo_workspace_deps::Config;
 use test_helpers::TestWorkspace;
 
 /// Test that version conflicts are detected and resolved across different sections
@@ -12,17 +12,9 @@ fn resolves_version_conflict_across_sections() -> Result<()> {
 
     workspace.run(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
-        check: false,
         version_resolution_strategy: cargo_workspace_deps::VersionResolutionStrategy::Highest,
-        output_format: OutputFormat::Text,
-        output_callback: None,
+        ..Default::default()
     })?;
 
     workspace.assert_matches("test_cross_section_conflict/after")?;
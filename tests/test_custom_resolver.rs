@@ -0,0 +1,189 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{Config, DependencyKey, Resolution, VersionResolver};
+use std::collections::HashMap;
+use test_helpers::TestWorkspace;
+
+/// Prefers whatever version a vendored mirror carries
+struct VendoredMirror {
+    versions: HashMap<&'static str, &'static str>,
+}
+
+impl VersionResolver for VendoredMirror {
+    fn name(&self) -> String {
+        "vendored mirror".to_string()
+    }
+
+    fn resolve(&self, key: &DependencyKey, versions: &HashMap<String, Vec<String>>) -> Resolution {
+        assert!(versions.len() > 1, "only conflicts are resolved");
+        match self.versions.get(key.name.as_str()) {
+            Some(version) => Resolution::Resolved(version.to_string()),
+            None => Resolution::Conflict(Some(format!("{} is not vendored", key.name))),
+        }
+    }
+}
+
+/// Hands back the same string for every dependency, valid requirement or not
+struct Verbatim(&'static str);
+
+impl VersionResolver for Verbatim {
+    fn name(&self) -> String {
+        "verbatim".to_string()
+    }
+
+    fn resolve(
+        &self,
+        _key: &DependencyKey,
+        _versions: &HashMap<String, Vec<String>>,
+    ) -> Resolution {
+        Resolution::Resolved(self.0.to_string())
+    }
+}
+
+#[test]
+fn custom_resolver_decides_conflicts() -> Result<()> {
+    let workspace = TestWorkspace::new("test_custom_resolver/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolver: Some(Box::new(VendoredMirror {
+            versions: HashMap::from([("anyhow", "1.0.80"), ("serde", "1.0.150")]),
+        })),
+        ..Default::default()
+    })?;
+
+    let expected = r#"Found 3 members
+
+Summary:
+  2 dependencies to consolidate
+  2 version conflicts resolved
+  1 conflicts could not resolve
+
+Will consolidate:
+  anyhow = "1.0.80" in: member1, member2, member3
+  serde = "1.0.150" in: member1, member2
+
+Resolved conflicts (using vendored mirror):
+  anyhow: 1.0.75, 1.0.80 → 1.0.80 (2 members move)
+    upgrade: member1, member2
+  serde: 1.0.100, 1.0.150 → 1.0.150 (1 member moves)
+    upgrade: member1
+
+Could not resolve:
+  log (version resolution):
+    0.4.17 in: member1
+    0.4.20 in: member2
+    note: log is not vendored
+
+Updating workspace Cargo.toml...
+Consolidated 2 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_custom_resolver/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn overrides_take_precedence_over_custom_resolver() -> Result<()> {
+    let workspace = TestWorkspace::new("test_custom_resolver/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolution_overrides: vec!["log=highest-compatible".parse().unwrap()],
+        version_resolver: Some(Box::new(VendoredMirror {
+            versions: HashMap::from([("anyhow", "1.0.80"), ("serde", "1.0.150")]),
+        })),
+        ..Default::default()
+    })?;
+
+    let expected = r#"Found 3 members
+
+Summary:
+  3 dependencies to consolidate
+  3 version conflicts resolved
+
+Will consolidate:
+  anyhow = "1.0.80" in: member1, member2, member3
+  log = "0.4.20" in: member1, member2
+  serde = "1.0.150" in: member1, member2
+
+Resolved conflicts (using vendored mirror):
+  anyhow: 1.0.75, 1.0.80 → 1.0.80 (2 members move)
+    upgrade: member1, member2
+  log: 0.4.17, 0.4.20 → 0.4.20 (using HighestCompatible, 1 member moves)
+    upgrade: member1
+  serde: 1.0.100, 1.0.150 → 1.0.150 (1 member moves)
+    upgrade: member1
+
+Updating workspace Cargo.toml...
+Consolidated 3 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    Ok(())
+}
+
+#[test]
+fn rejects_invalid_requirements_from_custom_resolver() -> Result<()> {
+    let workspace = TestWorkspace::new("test_custom_resolver/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolver: Some(Box::new(Verbatim("latest"))),
+        ..Default::default()
+    })?;
+
+    let expected = r#"Found 3 members
+
+Summary:
+  0 dependencies to consolidate
+  3 conflicts could not resolve
+  1 unused workspace dependencies
+
+No dependencies to consolidate.
+
+Could not resolve:
+  anyhow (version resolution):
+    1.0.75 in: member1, member2
+    1.0.80 in: member3
+    note: verbatim resolved to 'latest', which is not a valid version requirement
+  log (version resolution):
+    0.4.17 in: member1
+    0.4.20 in: member2
+    note: verbatim resolved to 'latest', which is not a valid version requirement
+  serde (version resolution):
+    1.0.100 in: member1, workspace
+    1.0.150 in: member2
+    note: verbatim resolved to 'latest', which is not a valid version requirement
+
+Unused workspace dependencies:
+  serde
+
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_custom_resolver/before")?;
+
+    Ok(())
+}
@@ -10,21 +10,83 @@ fn detects_default_features_conflict() -> Result<()> {
 
     workspace.run(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
-        check: false,
         version_resolution_strategy:
             cargo_workspace_deps::VersionResolutionStrategy::HighestCompatible,
-        output_format: OutputFormat::Text,
-        output_callback: None,
+        ..Default::default()
     })?;
 
     workspace.assert_matches("test_default_features_conflict/after")?;
 
     Ok(())
 }
+
+#[test]
+fn disables_default_features_to_resolve_conflict() -> Result<()> {
+    let workspace = TestWorkspace::new("test_default_features_resolution/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolution_strategy:
+            cargo_workspace_deps::VersionResolutionStrategy::HighestCompatible,
+        default_features_strategy: cargo_workspace_deps::DefaultFeaturesStrategy::Disable,
+        ..Default::default()
+    })?;
+
+    let expected = r#"Found 3 members
+
+Summary:
+  1 dependencies to consolidate
+  1 default-features conflicts resolved
+
+Will consolidate:
+  tokio = { version = "1.0", default-features = false } in: member1, member2, member3
+
+Resolved default-features conflicts (default-features = false):
+  tokio: features = ["default"] in: member2, member3
+
+Updating workspace Cargo.toml...
+Consolidated 1 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_default_features_resolution/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn records_default_features_resolution_in_json() -> Result<()> {
+    let workspace = TestWorkspace::new("test_default_features_resolution/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolution_strategy:
+            cargo_workspace_deps::VersionResolutionStrategy::HighestCompatible,
+        default_features_strategy: cargo_workspace_deps::DefaultFeaturesStrategy::Disable,
+        output_format: OutputFormat::Json,
+        ..Default::default()
+    })?;
+
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+    let tokio = &json["common_dependencies"][0];
+
+    assert_eq!(tokio["name"], "tokio");
+    assert_eq!(tokio["default_features"], false);
+    assert_eq!(
+        tokio["default_features_resolved_from"],
+        serde_json::json!({
+            "false": ["member1"],
+            "true": ["member2", "member3"],
+        })
+    );
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{CheckFailure, Config, OutputFormat};
+use test_helpers::TestWorkspace;
+
+#[test]
+fn demotes_entries_used_by_one_member() -> Result<()> {
+    let workspace = TestWorkspace::new("test_demote/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        demote: true,
+        ..Default::default()
+    })?;
+
+    // tokio is inherited by member2 only, but member3 is about to join it
+    let expected = r#"Found 3 members
+
+Summary:
+  1 dependencies to consolidate
+  1 unused workspace dependencies
+  1 workspace dependencies to demote
+
+Will consolidate:
+  tokio = "1.40" in: member3
+
+Unused workspace dependencies:
+  rand
+
+Will demote:
+  log to: member1
+
+Updating workspace Cargo.toml...
+Consolidated 1 dependencies
+Demoted 1 workspace dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_demote/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn shows_demotions_in_json() -> Result<()> {
+    let workspace = TestWorkspace::new("test_demote/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        demote: true,
+        output_format: OutputFormat::Json,
+        ..Default::default()
+    })?;
+
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+    assert_eq!(
+        json["demoted_dependencies"],
+        serde_json::json!([{ "name": "log", "members": ["member1"] }])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn check_fails_on_pending_demotions() -> Result<()> {
+    let workspace = TestWorkspace::new("test_demote/after")?;
+
+    // Raising the threshold leaves serde and tokio with too few members as well
+    let err = workspace
+        .run(Config {
+            check: true,
+            min_members: 3,
+            fix: true,
+            workspace_path: Some(workspace.path.clone()),
+            demote: true,
+            ..Default::default()
+        })
+        .unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<CheckFailure>(),
+        Some(CheckFailure::Demotion(2))
+    ));
+
+    workspace.assert_matches("test_demote/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn demotes_with_every_dependency_section_turned_off() -> Result<()> {
+    let workspace = TestWorkspace::new("test_demote/before")?;
+
+    workspace.run(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        process_dependencies: false,
+        process_dev_dependencies: false,
+        process_build_dependencies: false,
+        demote: true,
+        output_callback: Some(Box::new(|_| {})),
+        ..Default::default()
+    })?;
+
+    // member3 is not joining tokio this time, so member2 gets it back too
+    workspace.assert_matches("test_demote/after_only")?;
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 mod test_helpers;
 
 use anyhow::Result;
-use cargo_workspace_deps::{Config, OutputFormat};
+use cargo_workspace_deps::Config;
 use test_helpers::TestWorkspace;
 
 #[test]
@@ -10,17 +10,9 @@ fn handles_no_common_dependencies() -> Result<()> {
 
     workspace.run(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
-        check: false,
         version_resolution_strategy: cargo_workspace_deps::VersionResolutionStrategy::Skip,
-        output_format: OutputFormat::Text,
-        output_callback: None,
+        ..Default::default()
     })?;
 
     workspace.assert_matches("test_no_common/after")?;
@@ -34,17 +26,9 @@ fn handles_single_member_workspace() -> Result<()> {
 
     workspace.run(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
-        check: false,
         version_resolution_strategy: cargo_workspace_deps::VersionResolutionStrategy::Skip,
-        output_format: OutputFormat::Text,
-        output_callback: None,
+        ..Default::default()
     })?;
 
     workspace.assert_matches("test_single_member/after")?;
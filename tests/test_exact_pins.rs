@@ -0,0 +1,111 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{Config, ExactPinPolicy, OutputFormat};
+use test_helpers::TestWorkspace;
+
+#[test]
+fn reports_overridden_pins_as_conflicts() -> Result<()> {
+    let workspace = TestWorkspace::new("test_exact_pins/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        exact_pin_policy: ExactPinPolicy::Conflict,
+        ..Default::default()
+    })?;
+
+    let expected = r#"Found 3 members
+
+Summary:
+  1 dependencies to consolidate
+  1 version conflicts resolved
+  1 conflicts could not resolve
+
+Will consolidate:
+  serde = "1.0.200" in: member1, member2, member3
+
+Resolved conflicts (using HighestCompatible):
+  serde: 1.0.150, 1.0.200 → 1.0.200 (2 members move)
+    upgrade: member1, member2
+
+Could not resolve:
+  tokio (exact pin):
+    1.40 in: member2, member3
+    =1.38.0 in: member1
+
+Updating workspace Cargo.toml...
+Consolidated 1 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_exact_pins/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn applies_pin_to_workspace() -> Result<()> {
+    let workspace = TestWorkspace::new("test_exact_pins/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        exact_pin_policy: ExactPinPolicy::Win,
+        ..Default::default()
+    })?;
+
+    assert!(
+        output
+            .contains("  tokio = \"=1.38.0\" in: member1, member2, member3 (pinned by member1)\n")
+    );
+
+    workspace.assert_matches("test_exact_pins/after_win")?;
+
+    Ok(())
+}
+
+#[test]
+fn leaves_pinned_members_out() -> Result<()> {
+    let workspace = TestWorkspace::new("test_exact_pins/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        exact_pin_policy: ExactPinPolicy::ExcludeMember,
+        ..Default::default()
+    })?;
+
+    assert!(output.contains("  tokio = \"1.40\" in: member2, member3 (member1 keeps =1.38.0)\n"));
+
+    workspace.assert_matches("test_exact_pins/after_exclude_member")?;
+
+    Ok(())
+}
+
+#[test]
+fn shows_pins_in_json() -> Result<()> {
+    let workspace = TestWorkspace::new("test_exact_pins/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        exact_pin_policy: ExactPinPolicy::ExcludeMember,
+        output_format: OutputFormat::Json,
+        ..Default::default()
+    })?;
+
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+    let tokio = &json["common_dependencies"][1];
+
+    assert_eq!(tokio["name"], "tokio");
+    assert_eq!(tokio["members"], serde_json::json!(["member2", "member3"]));
+    assert_eq!(tokio["pins"], serde_json::json!({ "=1.38.0": ["member1"] }));
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 mod test_helpers;
 
 use anyhow::Result;
-use cargo_workspace_deps::{Config, OutputFormat};
+use cargo_workspace_deps::Config;
 use test_helpers::TestWorkspace;
 
 #[test]
@@ -10,17 +10,10 @@ fn excludes_members_by_glob_pattern() -> Result<()> {
 
     workspace.run(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
         exclude_members: vec![glob::Pattern::new("test-*").unwrap()],
-        check: false,
         version_resolution_strategy: cargo_workspace_deps::VersionResolutionStrategy::Skip,
-        output_format: OutputFormat::Text,
-        output_callback: None,
+        ..Default::default()
     })?;
 
     workspace.assert_matches("test_exclude_members/after")?;
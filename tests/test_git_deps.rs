@@ -1,29 +1,18 @@
 mod test_helpers;
 
 use anyhow::Result;
-use cargo_workspace_deps::{Config, OutputFormat};
+use cargo_workspace_deps::Config;
 use test_helpers::TestWorkspace;
 
-// TODO: Git deps are currently not consolidated. They remain in each member's Cargo.toml.
-// For now, we just verify that we don't touch them.
-
 #[test]
-fn skips_git_dependencies() -> Result<()> {
+fn consolidates_git_dependencies() -> Result<()> {
     let workspace = TestWorkspace::new("test_git_deps/before")?;
 
     workspace.run(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
-        check: false,
         version_resolution_strategy: cargo_workspace_deps::VersionResolutionStrategy::Skip,
-        output_format: OutputFormat::Text,
-        output_callback: None,
+        ..Default::default()
     })?;
 
     workspace.assert_matches("test_git_deps/after")?;
@@ -32,25 +21,58 @@ fn skips_git_dependencies() -> Result<()> {
 }
 
 #[test]
-fn skips_mixed_version_and_git() -> Result<()> {
+fn consolidates_mixed_version_and_git() -> Result<()> {
     let workspace = TestWorkspace::new("test_mixed_version_git/before")?;
 
     workspace.run(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
-        check: false,
         version_resolution_strategy: cargo_workspace_deps::VersionResolutionStrategy::Skip,
-        output_format: OutputFormat::Text,
-        output_callback: None,
+        ..Default::default()
     })?;
 
     workspace.assert_matches("test_mixed_version_git/after")?;
 
     Ok(())
 }
+
+#[test]
+fn groups_git_dependencies_by_repository_and_reference() -> Result<()> {
+    let workspace = TestWorkspace::new("test_git_references/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolution_strategy: cargo_workspace_deps::VersionResolutionStrategy::Skip,
+        ..Default::default()
+    })?;
+    assert!(output.contains(
+        "  forked = { git = \"https://github.com/example/forked\", rev = \"abc123\" } in: member1, member2, member3, member4\n"
+    ));
+    assert!(output.contains(
+        "  pinned (git reference differs):\n    branch = \"dev\" in: member2\n    branch = \"main\" in: member1\n"
+    ));
+
+    workspace.assert_matches("test_git_references/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn reports_registry_and_git_sources_of_one_name() -> Result<()> {
+    let workspace = TestWorkspace::new("test_mixed_sources/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        ..Default::default()
+    })?;
+    // The crates.io members must not be moved onto the git fork
+    assert!(output.contains(
+        "  foo (git reference differs):\n    tag = \"v2\" in: member3, member4\n    1.0 in: member1, member2\n    note: foo comes from more than one source: crates.io, github.com/example/foo\n"
+    ), "{output}");
+
+    workspace.assert_matches("test_mixed_sources/after")?;
+
+    Ok(())
+}
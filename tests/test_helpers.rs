@@ -2,8 +2,10 @@
 
 use anyhow::Result;
 use cargo_workspace_deps::{Config, run};
+use std::cell::RefCell;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
 pub struct TestWorkspace {
     pub path: PathBuf,
@@ -34,6 +36,21 @@ impl TestWorkspace {
         run(config)
     }
 
+    /// Run the tool with the given config, returning what it printed
+    pub fn run_captured(&self, config: Config) -> Result<String> {
+        let captured = Rc::new(RefCell::new(String::new()));
+        let captured_clone = captured.clone();
+
+        run(Config {
+            output_callback: Some(Box::new(move |s| {
+                captured_clone.borrow_mut().push_str(s);
+            })),
+            ..config
+        })?;
+
+        Ok(captured.take())
+    }
+
     /// Assert that workspace matches expected fixture
     pub fn assert_matches(&self, expected_fixture: &str) -> Result<()> {
         let expected_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
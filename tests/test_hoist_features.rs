@@ -0,0 +1,90 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{Config, OutputFormat};
+use test_helpers::TestWorkspace;
+
+#[test]
+fn hoists_features_shared_by_every_member() -> Result<()> {
+    let workspace = TestWorkspace::new("test_hoist_features/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        hoist_features: true,
+        ..Default::default()
+    })?;
+
+    let expected = r#"Found 2 members
+
+Summary:
+  3 dependencies to consolidate
+
+Will consolidate:
+  anyhow = "1.0" in: member1, member2
+  serde = { version = "1.0", features = ["derive"] } in: member1, member2
+  tokio = "1.0" in: member1, member2
+
+Updating workspace Cargo.toml...
+Consolidated 3 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_hoist_features/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn reports_hoisted_features_in_json() -> Result<()> {
+    let workspace = TestWorkspace::new("test_hoist_features/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        hoist_features: true,
+        output_format: OutputFormat::Json,
+        ..Default::default()
+    })?;
+
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+    let features: Vec<_> = json["common_dependencies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|dep| (dep["name"].as_str().unwrap(), dep.get("features").cloned()))
+        .collect();
+
+    assert_eq!(
+        features,
+        vec![
+            ("anyhow", None),
+            ("serde", Some(serde_json::json!(["derive"]))),
+            ("tokio", None),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn hoisting_again_changes_nothing() -> Result<()> {
+    let workspace = TestWorkspace::new("test_hoist_features/after")?;
+
+    workspace.run(Config {
+        output_callback: Some(Box::new(|_| {})),
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        hoist_features: true,
+        ..Default::default()
+    })?;
+
+    workspace.assert_matches("test_hoist_features/after")?;
+
+    Ok(())
+}
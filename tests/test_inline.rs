@@ -0,0 +1,74 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{InlineConfig, inline};
+use std::cell::RefCell;
+use std::rc::Rc;
+use test_helpers::TestWorkspace;
+
+fn patterns(names: &[&str]) -> Vec<glob::Pattern> {
+    names
+        .iter()
+        .map(|name| glob::Pattern::new(name).unwrap())
+        .collect()
+}
+
+#[test]
+fn inlines_workspace_entries_into_members() -> Result<()> {
+    let workspace = TestWorkspace::new("test_inline/before")?;
+
+    let captured = Rc::new(RefCell::new(String::new()));
+    let captured_clone = captured.clone();
+
+    inline(InlineConfig {
+        workspace_path: Some(workspace.path.clone()),
+        dependencies: patterns(&["serde", "tok*", "json", "member3"]),
+        output_callback: Some(Box::new(move |s| {
+            captured_clone.borrow_mut().push_str(s);
+        })),
+    })?;
+
+    let output = captured.borrow().clone();
+
+    let expected = r#"Found 3 members
+
+Inlined:
+  json in: member2
+  member3 in: member1
+  serde in: member1, member2, member3
+  tokio in: member1, member2
+
+Removed from [workspace.dependencies]: json, member3, serde, tokio
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_inline/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn fails_on_unknown_dependency() -> Result<()> {
+    let workspace = TestWorkspace::new("test_inline/before")?;
+
+    let err = inline(InlineConfig {
+        workspace_path: Some(workspace.path.clone()),
+        dependencies: patterns(&["serde", "rand*"]),
+        output_callback: Some(Box::new(|_| {})),
+    })
+    .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "No entry in [workspace.dependencies] matches 'rand*'"
+    );
+
+    workspace.assert_matches("test_inline/before")?;
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{Config, VersionResolutionStrategy};
+use test_helpers::TestWorkspace;
+
+#[test]
+fn writes_intersection_of_requirements() -> Result<()> {
+    let workspace = TestWorkspace::new("test_intersect/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolution_strategy: VersionResolutionStrategy::Intersect,
+        ..Default::default()
+    })?;
+
+    let expected = r#"Found 2 members
+
+Summary:
+  3 dependencies to consolidate
+  3 version conflicts resolved
+  1 conflicts could not resolve
+
+Will consolidate:
+  log = "0.4.20" in: member1, member2
+  serde = "1.0.150" in: member1, member2
+  tokio = "~1.4.1" in: member1, member2
+
+Resolved conflicts (using Intersect):
+  log: 0.4.17, ~0.4.20 → 0.4.20 (2 members move)
+    upgrade: member2
+  serde: (>=1.0.100, <2), >=1.0.150 → 1.0.150 (2 members move)
+    upgrade: member1
+  tokio: 1.4.1, (>=1.2, <1.5) → ~1.4.1 (2 members move)
+    upgrade: member1
+
+Could not resolve:
+  regex (version resolution):
+    1 in: member1
+    2 in: member2
+    note: No version satisfies all requirements: '1' (member1) and '2' (member2) do not overlap
+
+Updating workspace Cargo.toml...
+Consolidated 3 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_intersect/after")?;
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{Config, VersionResolutionStrategy};
+use std::path::PathBuf;
+use test_helpers::TestWorkspace;
+
+fn fake_registry() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/test_latest_compatible/registry")
+}
+
+#[test]
+fn resolves_to_newest_published_version() -> Result<()> {
+    let workspace = TestWorkspace::new("test_latest_compatible/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolution_strategy: VersionResolutionStrategy::LatestCompatible,
+        registry_index: Some(fake_registry()),
+        ..Default::default()
+    })?;
+
+    // serde: 1.0.220 is yanked and 1.1.0 is outside ~1.0.150
+    // anyhow: not in the index, resolved with highest-compatible
+    let expected = r#"Found 2 members
+
+Summary:
+  3 dependencies to consolidate
+  3 version conflicts resolved
+
+Will consolidate:
+  anyhow = "1.0.80" in: member1, member2
+  log = "0.4.22" in: member1, member2
+  serde = "~1.0.210" in: member1, member2
+
+Resolved conflicts (using LatestCompatible):
+  anyhow: 1.0.75, 1.0.80 → 1.0.80 (1 member moves)
+    upgrade: member1
+  log: 0.4.17, 0.4.20 → 0.4.22 (2 members move)
+    upgrade: member1, member2
+  serde: 1.0.100, ~1.0.150 → ~1.0.210 (2 members move)
+    upgrade: member1, member2
+
+Updating workspace Cargo.toml...
+Consolidated 3 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_latest_compatible/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn rejects_missing_registry_directory() -> Result<()> {
+    let workspace = TestWorkspace::new("test_latest_compatible/before")?;
+
+    let err = workspace
+        .run(Config {
+            fix: true,
+            workspace_path: Some(workspace.path.clone()),
+            version_resolution_strategy: VersionResolutionStrategy::LatestCompatible,
+            registry_index: Some(workspace.path.join("missing")),
+            ..Default::default()
+        })
+        .unwrap_err();
+    assert!(err.to_string().ends_with("is not a directory"), "{}", err);
+
+    workspace.assert_matches("test_latest_compatible/before")?;
+
+    Ok(())
+}
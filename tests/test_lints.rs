@@ -0,0 +1,132 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::Config;
+use test_helpers::TestWorkspace;
+
+#[test]
+fn consolidates_shared_lint_tables() -> Result<()> {
+    let workspace = TestWorkspace::new("test_lints/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        process_lints: true,
+        ..Default::default()
+    })?;
+
+    let expected = r#"Found 3 members
+
+Summary:
+  0 dependencies to consolidate
+  1 lint table to consolidate
+  1 members with differing lint tables
+
+No dependencies to consolidate.
+
+Will consolidate lints in: member1, member2
+  clippy::pedantic = { level = "warn", priority = -1 }
+  clippy::unwrap_used = "deny"
+  rust::unsafe_code = "forbid"
+
+Lint tables differ:
+  member3:
+    clippy::unwrap_used = "warn" (expected "deny")
+    clippy::pedantic = { level = "warn", priority = -1 } (missing)
+
+Updating workspace Cargo.toml...
+Consolidated 0 dependencies and 1 lint table
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_lints/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn check_fails_when_lints_can_be_consolidated() -> Result<()> {
+    let workspace = TestWorkspace::new("test_lints/before")?;
+
+    let result = workspace.run(Config {
+        workspace_path: Some(workspace.path.clone()),
+        check: true,
+        process_lints: true,
+        output_callback: Some(Box::new(|_| {})),
+        ..Default::default()
+    });
+
+    let err = result.unwrap_err();
+    assert!(err.to_string().contains("Check failed"));
+
+    workspace.assert_matches("test_lints/before")?;
+
+    Ok(())
+}
+
+#[test]
+fn leaves_lints_alone_by_default() -> Result<()> {
+    let workspace = TestWorkspace::new("test_lints/before")?;
+
+    workspace.run(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        output_callback: Some(Box::new(|_| {})),
+        ..Default::default()
+    })?;
+
+    workspace.assert_matches("test_lints/before")?;
+
+    Ok(())
+}
+
+#[test]
+fn consolidates_lints_most_members_agree_on() -> Result<()> {
+    let workspace = TestWorkspace::new("test_lints_majority/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        process_lints: true,
+        ..Default::default()
+    })?;
+
+    // Two tables with two members each, the extra lint is not hoisted
+    let expected = r#"Found 4 members
+
+Summary:
+  0 dependencies to consolidate
+  1 lint table to consolidate
+  2 members with differing lint tables
+
+No dependencies to consolidate.
+
+Will consolidate lints in: member1, member2
+  clippy::unwrap_used = "deny"
+  rust::unsafe_code = "forbid"
+
+Lint tables differ:
+  member3:
+    clippy::expect_used = "warn" (extra)
+  member4:
+    clippy::expect_used = "warn" (extra)
+
+Updating workspace Cargo.toml...
+Consolidated 0 dependencies and 1 lint table
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_lints_majority/after")?;
+
+    Ok(())
+}
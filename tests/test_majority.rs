@@ -0,0 +1,52 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{Config, VersionResolutionStrategy};
+use test_helpers::TestWorkspace;
+
+#[test]
+fn resolves_to_most_used_version() -> Result<()> {
+    let workspace = TestWorkspace::new("test_majority/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolution_strategy: VersionResolutionStrategy::Majority,
+        ..Default::default()
+    })?;
+
+    // serde: the workspace entry breaks the tie in favor of 1.0.100
+    // log: tied, resolved with highest-compatible
+    let expected = r#"Found 3 members
+
+Summary:
+  3 dependencies to consolidate
+  3 version conflicts resolved
+
+Will consolidate:
+  anyhow = "1.0.75" in: member1, member2, member3
+  log = "0.4.20" in: member1, member2
+  serde = "1.0.100" in: member1, member2
+
+Resolved conflicts (using Majority):
+  anyhow: 1.0.75, 1.0.80 → 1.0.75 (1 member moves)
+    downgrade: member3
+  log: 0.4.17, 0.4.20 → 0.4.20 (1 member moves)
+    upgrade: member1
+  serde: 1.0.100, 1.0.150 → 1.0.100 (1 member moves)
+    downgrade: member2
+
+Updating workspace Cargo.toml...
+Consolidated 3 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_majority/after")?;
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{Config, OutputFormat};
+use test_helpers::TestWorkspace;
+
+#[test]
+fn applies_member_metadata() -> Result<()> {
+    let workspace = TestWorkspace::new("test_member_metadata/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        demote: true,
+        ..Default::default()
+    })?;
+
+    // log is only inherited by the skipped member3, so it is not demoted into it
+    let expected = r#"Found 3 members
+
+Summary:
+  1 dependencies to consolidate
+
+Will consolidate:
+  serde = "1.0" in: member1, member2
+
+Skipped by member metadata:
+  member2: tempfile (no-dev-dependencies)
+  member2: tokio (exclude)
+  member3: rand, serde, tempfile (skip)
+
+Updating workspace Cargo.toml...
+Consolidated 1 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_member_metadata/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn shows_skip_reasons_in_json() -> Result<()> {
+    let workspace = TestWorkspace::new("test_member_metadata/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        demote: true,
+        output_format: OutputFormat::Json,
+        ..Default::default()
+    })?;
+
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+    assert_eq!(
+        json["skipped_by_members"],
+        serde_json::json!([
+            { "member": "member2", "reason": "no-dev-dependencies", "dependencies": ["tempfile"] },
+            { "member": "member2", "reason": "exclude", "dependencies": ["tokio"] },
+            { "member": "member3", "reason": "skip", "dependencies": ["rand", "serde", "tempfile"] },
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn leaves_skipped_member_package_and_lints_alone() -> Result<()> {
+    let workspace = TestWorkspace::new("test_member_metadata/before")?;
+
+    for member in ["member1", "member2", "member3"] {
+        let manifest_path = workspace.path.join(member).join("Cargo.toml");
+        let manifest = std::fs::read_to_string(&manifest_path)?;
+        std::fs::write(
+            &manifest_path,
+            format!("{manifest}\n[lints.clippy]\nunwrap_used = \"deny\"\n"),
+        )?;
+    }
+    let skipped_path = workspace.path.join("member3/Cargo.toml");
+    let skipped_before = std::fs::read_to_string(&skipped_path)?;
+
+    workspace.run(Config {
+        process_package: true,
+        process_lints: true,
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        demote: true,
+        ..Default::default()
+    })?;
+
+    let member1 = std::fs::read_to_string(workspace.path.join("member1/Cargo.toml"))?;
+    assert!(member1.contains("edition.workspace = true"), "{member1}");
+    assert!(member1.contains("[lints]\nworkspace = true"), "{member1}");
+    assert_eq!(std::fs::read_to_string(&skipped_path)?, skipped_before);
+
+    Ok(())
+}
+
+#[test]
+fn reports_invalid_member_metadata_with_location() -> Result<()> {
+    let workspace = TestWorkspace::new("test_member_metadata/before")?;
+
+    let manifest_path = workspace.path.join("member3/Cargo.toml");
+    let manifest = std::fs::read_to_string(&manifest_path)?;
+    std::fs::write(
+        &manifest_path,
+        manifest.replace("skip = true", "skip = \"yes\""),
+    )?;
+
+    let err = workspace
+        .run(Config {
+            fix: true,
+            workspace_path: Some(workspace.path.clone()),
+            demote: true,
+            ..Default::default()
+        })
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        format!("{}:8:8: 'skip' must be a boolean", manifest_path.display())
+    );
+
+    Ok(())
+}
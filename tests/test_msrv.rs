@@ -0,0 +1,66 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{Config, VersionResolutionStrategy};
+use std::path::PathBuf;
+use test_helpers::TestWorkspace;
+
+#[test]
+fn skips_versions_above_rust_version() -> Result<()> {
+    // The fake index knows clap, serde and tokio, and nothing about anyhow
+    let registry_index =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/test_msrv/registry");
+
+    let workspace = TestWorkspace::new("test_msrv/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolution_strategy: VersionResolutionStrategy::Highest,
+        respect_rust_version: true,
+        registry_index: Some(registry_index),
+        ..Default::default()
+    })?;
+
+    // member2 declares rust-version 1.60, lower than the workspace's 1.65
+    let expected = r#"Found 2 members
+
+Summary:
+  2 dependencies to consolidate
+  2 version conflicts resolved
+  2 conflicts could not resolve
+
+Will consolidate:
+  anyhow = "1.0.80" in: member1, member2
+  serde = "1.0.100" in: member1, member2
+
+Resolved conflicts (using Highest):
+  anyhow: 1.0.75, 1.0.80 → 1.0.80 (1 member moves)
+    upgrade: member1
+  serde: 1.0.100, 1.0.200 → 1.0.100 (1 member moves)
+    downgrade: member2
+
+Could not resolve:
+  clap (rust-version):
+    3.2 in: member1
+    4.0 in: member2
+    note: '4.0' needs 1.64, moving it to '3.2' would be breaking
+  tokio (rust-version):
+    1.38 in: member1
+    1.40 in: member2
+    note: No version builds with rust-version 1.60: '1.38' needs 1.63, '1.40' needs 1.70
+
+Updating workspace Cargo.toml...
+Consolidated 2 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_msrv/after")?;
+
+    Ok(())
+}
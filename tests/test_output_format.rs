@@ -2,8 +2,6 @@ mod test_helpers;
 
 use anyhow::Result;
 use cargo_workspace_deps::{Config, OutputFormat, VersionResolutionStrategy};
-use std::cell::RefCell;
-use std::rc::Rc;
 use test_helpers::TestWorkspace;
 
 /// Test that text output format produces the expected output
@@ -11,28 +9,13 @@ use test_helpers::TestWorkspace;
 fn test_text_output_format() -> Result<()> {
     let workspace = TestWorkspace::new("test_output_comprehensive/before")?;
 
-    let captured = Rc::new(RefCell::new(String::new()));
-    let captured_clone = captured.clone();
-
-    workspace.run(Config {
+    let output = workspace.run_captured(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
-        check: false,
         version_resolution_strategy: VersionResolutionStrategy::Highest,
-        output_format: OutputFormat::Text,
-        output_callback: Some(Box::new(move |s| {
-            captured_clone.borrow_mut().push_str(s);
-        })),
+        ..Default::default()
     })?;
 
-    let output = captured.borrow().clone();
-
     let expected = r#"Found 3 members
 
 Summary:
@@ -43,15 +26,18 @@ Summary:
 
 Will consolidate:
   anyhow = "1.0.80" in: member1, member2, member3
-  bindgen = "0.70.0" in: member1, member2, member3
-  lazy_static = "1.5.0" in: member1, member2, member3
+  bindgen = "0.70" in: member1, member2, member3
+  lazy_static = { version = "1.5", default-features = false } in: member1, member2, member3
   rstest = "0.23" in: member1, member2, member3
   serde = "1.0" in: member1, member2, member3
 
 Resolved conflicts (using Highest):
-  anyhow: 1.0.75, 1.0.78, 1.0.80 → 1.0.80
-  bindgen: 0.69, 0.70 → 0.70.0
-  lazy_static: 1.4, 1.5 → 1.5.0
+  anyhow: 1.0.75, 1.0.78, 1.0.80 → 1.0.80 (2 members move)
+    upgrade: member1, member3
+  bindgen: 0.69, 0.70 → 0.70 (2 members move)
+    breaking: member1, member3
+  lazy_static: 1.4, 1.5 → 1.5 (2 members move)
+    upgrade: member1, member3
 
 Could not resolve:
   tokio (default-features differ):
@@ -82,28 +68,14 @@ Consolidated 5 dependencies
 fn test_json_output_format() -> Result<()> {
     let workspace = TestWorkspace::new("test_output_comprehensive/before")?;
 
-    let captured = Rc::new(RefCell::new(String::new()));
-    let captured_clone = captured.clone();
-
-    workspace.run(Config {
+    let output = workspace.run_captured(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
-        check: false,
         version_resolution_strategy: VersionResolutionStrategy::Highest,
         output_format: OutputFormat::Json,
-        output_callback: Some(Box::new(move |s| {
-            captured_clone.borrow_mut().push_str(s);
-        })),
+        ..Default::default()
     })?;
 
-    let output = captured.borrow().clone();
-
     // Parse and verify the JSON structure
     let mut json: serde_json::Value = serde_json::from_str(&output)?;
 
@@ -116,6 +88,11 @@ fn test_json_output_format() -> Result<()> {
   "common_dependencies": [
     {
       "default_features": true,
+      "member_changes": {
+        "member1": "upgrade",
+        "member2": "unchanged",
+        "member3": "upgrade"
+      },
       "members": [
         "member1",
         "member2",
@@ -137,6 +114,11 @@ fn test_json_output_format() -> Result<()> {
     },
     {
       "default_features": true,
+      "member_changes": {
+        "member1": "breaking",
+        "member2": "unchanged",
+        "member3": "breaking"
+      },
       "members": [
         "member1",
         "member2",
@@ -152,10 +134,15 @@ fn test_json_output_format() -> Result<()> {
           "member2"
         ]
       },
-      "version": "0.70.0"
+      "version": "0.70"
     },
     {
-      "default_features": true,
+      "default_features": false,
+      "member_changes": {
+        "member1": "upgrade",
+        "member2": "unchanged",
+        "member3": "upgrade"
+      },
       "members": [
         "member1",
         "member2",
@@ -171,7 +158,7 @@ fn test_json_output_format() -> Result<()> {
           "member2"
         ]
       },
-      "version": "1.5.0"
+      "version": "1.5"
     },
     {
       "default_features": true,
@@ -0,0 +1,110 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::Config;
+use test_helpers::TestWorkspace;
+
+#[test]
+fn consolidates_shared_package_fields() -> Result<()> {
+    let workspace = TestWorkspace::new("test_package/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        process_package: true,
+        ..Default::default()
+    })?;
+
+    let expected = r#"Found 3 members
+
+Summary:
+  0 dependencies to consolidate
+  4 package fields to consolidate
+  2 package fields could not resolve
+
+No dependencies to consolidate.
+
+Will consolidate package fields:
+  authors = ["Jane Doe <jane@example.com>"] in: member1, member2
+  edition = "2021" in: member1, member2, member3
+  license = "MIT OR Apache-2.0" in: member1, member2
+  repository = "https://github.com/example/repo" in: member1, member2, member3
+
+Could not resolve package fields:
+  license:
+    "MIT" in: member3
+  rust-version:
+    "1.70" in: member1
+    "1.74" in: member2
+
+Updating workspace Cargo.toml...
+Consolidated 0 dependencies and 4 package fields
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_package/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn check_fails_when_package_fields_can_be_consolidated() -> Result<()> {
+    let workspace = TestWorkspace::new("test_package/before")?;
+
+    let result = workspace.run(Config {
+        workspace_path: Some(workspace.path.clone()),
+        check: true,
+        process_package: true,
+        output_callback: Some(Box::new(|_| {})),
+        ..Default::default()
+    });
+
+    let err = result.unwrap_err();
+    assert!(err.to_string().contains("Check failed"));
+
+    workspace.assert_matches("test_package/before")?;
+
+    Ok(())
+}
+
+#[test]
+fn leaves_package_fields_alone_by_default() -> Result<()> {
+    let workspace = TestWorkspace::new("test_package/before")?;
+
+    workspace.run(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        output_callback: Some(Box::new(|_| {})),
+        ..Default::default()
+    })?;
+
+    workspace.assert_matches("test_package/before")?;
+
+    Ok(())
+}
+
+#[test]
+fn consolidates_package_fields_most_members_share() -> Result<()> {
+    let workspace = TestWorkspace::new("test_package_outlier/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        process_package: true,
+        ..Default::default()
+    })?;
+
+    // member4 keeps its own edition instead of holding back the others
+    assert!(output.contains(
+        "Will consolidate package fields:\n  edition = \"2021\" in: member1, member2, member3\n\nCould not resolve package fields:\n  edition:\n    \"2018\" in: member4\n"
+    ), "{output}");
+
+    workspace.assert_matches("test_package_outlier/after")?;
+
+    Ok(())
+}
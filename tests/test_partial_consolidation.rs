@@ -0,0 +1,104 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{Config, OutputFormat};
+use test_helpers::TestWorkspace;
+
+#[test]
+fn consolidates_largest_compatible_group() -> Result<()> {
+    let workspace = TestWorkspace::new("test_partial_consolidation/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        partial_consolidation: true,
+        ..Default::default()
+    })?;
+
+    // rand has one member per major version, so no group is large enough
+    let expected = r#"Found 4 members
+
+Summary:
+  1 dependencies to consolidate
+  1 version conflicts resolved
+  1 dependencies with diverging members
+  1 conflicts could not resolve
+
+Will consolidate:
+  serde = "1.0.100" in: member1, member2, member3
+
+Resolved conflicts (using HighestCompatible):
+  serde: 1.0, 1.0.100 → 1.0.100 (2 members move)
+    upgrade: member1, member3
+
+Diverging members (keep their own version):
+  serde:
+    0.9 in: member4
+
+Could not resolve:
+  rand (version resolution):
+    0.7 in: member1
+    0.8 in: member2
+    note: No version satisfies all requirements: '0.7' (member1) and '0.8' (member2) do not overlap
+
+Updating workspace Cargo.toml...
+Consolidated 1 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_partial_consolidation/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn reports_conflict_without_partial_consolidation() -> Result<()> {
+    let workspace = TestWorkspace::new("test_partial_consolidation/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        ..Default::default()
+    })?;
+
+    assert!(output.contains("  serde (version resolution):\n"));
+    assert!(!output.contains("Diverging members"));
+
+    workspace.assert_matches("test_partial_consolidation/before")?;
+
+    Ok(())
+}
+
+#[test]
+fn shows_diverging_members_in_json() -> Result<()> {
+    let workspace = TestWorkspace::new("test_partial_consolidation/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        partial_consolidation: true,
+        output_format: OutputFormat::Json,
+        ..Default::default()
+    })?;
+
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+    let serde = &json["common_dependencies"][0];
+
+    assert_eq!(serde["name"], "serde");
+    assert_eq!(
+        serde["members"],
+        serde_json::json!(["member1", "member2", "member3"])
+    );
+    assert_eq!(
+        serde["diverging_members"],
+        serde_json::json!({ "0.9": ["member4"] })
+    );
+    assert_eq!(json["conflicts"][0]["name"], "rand");
+
+    Ok(())
+}
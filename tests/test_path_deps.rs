@@ -1,29 +1,18 @@
 mod test_helpers;
 
 use anyhow::Result;
-use cargo_workspace_deps::{Config, OutputFormat};
+use cargo_workspace_deps::Config;
 use test_helpers::TestWorkspace;
 
-// TODO: Path deps are currently not consolidated. They remain in each member's Cargo.toml.
-// For now, we just verify that we don't touch them.
-
 #[test]
 fn skips_path_dependencies() -> Result<()> {
     let workspace = TestWorkspace::new("test_path_deps/before")?;
 
     workspace.run(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
-        check: false,
         version_resolution_strategy: cargo_workspace_deps::VersionResolutionStrategy::Skip,
-        output_format: OutputFormat::Text,
-        output_callback: None,
+        ..Default::default()
     })?;
 
     workspace.assert_matches("test_path_deps/after")?;
@@ -37,20 +26,65 @@ fn skips_mixed_version_and_path() -> Result<()> {
 
     workspace.run(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
-        check: false,
         version_resolution_strategy: cargo_workspace_deps::VersionResolutionStrategy::Skip,
-        output_format: OutputFormat::Text,
-        output_callback: None,
+        ..Default::default()
     })?;
 
     workspace.assert_matches("test_mixed_version_path/after")?;
 
     Ok(())
 }
+
+#[test]
+fn hoists_path_dependencies() -> Result<()> {
+    let workspace = TestWorkspace::new("test_path_deps/before")?;
+
+    workspace.run(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolution_strategy: cargo_workspace_deps::VersionResolutionStrategy::Skip,
+        consolidate_path_dependencies: true,
+        ..Default::default()
+    })?;
+
+    workspace.assert_matches("test_path_deps/after_path_dependencies")?;
+
+    Ok(())
+}
+
+#[test]
+fn hoists_mixed_version_and_path() -> Result<()> {
+    let workspace = TestWorkspace::new("test_mixed_version_path/before")?;
+
+    workspace.run(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolution_strategy: cargo_workspace_deps::VersionResolutionStrategy::Skip,
+        consolidate_path_dependencies: true,
+        ..Default::default()
+    })?;
+
+    workspace.assert_matches("test_mixed_version_path/after_path_dependencies")?;
+
+    Ok(())
+}
+
+#[test]
+fn hoists_members_used_by_one_sibling_or_excluded() -> Result<()> {
+    let workspace = TestWorkspace::new("test_path_member_deps/before")?;
+
+    // member2 has a single dependent and member3 is left out by pattern,
+    // yet both are members siblings depend on
+    workspace.run(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        exclude_members: vec![glob::Pattern::new("member3")?],
+        consolidate_path_dependencies: true,
+        ..Default::default()
+    })?;
+
+    workspace.assert_matches("test_path_member_deps/after")?;
+
+    Ok(())
+}
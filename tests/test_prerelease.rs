@@ -0,0 +1,155 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{Config, PrereleasePolicy, VersionResolutionStrategy};
+use test_helpers::TestWorkspace;
+
+#[test]
+fn prefers_stable_versions() -> Result<()> {
+    let workspace = TestWorkspace::new("test_prerelease/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolution_strategy: VersionResolutionStrategy::Highest,
+        prerelease_policy: PrereleasePolicy::PreferStable,
+        ..Default::default()
+    })?;
+
+    let expected = r#"Found 2 members
+
+Summary:
+  2 dependencies to consolidate
+  2 version conflicts resolved
+
+Will consolidate:
+  serde = "1.0.150" in: member1, member2
+  tokio = "2.0.0-rc.2" in: member1, member2
+
+Resolved conflicts (using Highest):
+  serde: 1.0.150, 1.1.0-alpha.1 → 1.0.150 (1 member moves)
+    downgrade: member2
+  tokio: 2.0.0-rc.1, 2.0.0-rc.2 → 2.0.0-rc.2 (1 member moves)
+    upgrade: member1
+
+Updating workspace Cargo.toml...
+Consolidated 2 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_prerelease/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn denies_prerelease_only_dependencies() -> Result<()> {
+    let workspace = TestWorkspace::new("test_prerelease/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolution_strategy: VersionResolutionStrategy::Highest,
+        prerelease_policy: PrereleasePolicy::Deny,
+        ..Default::default()
+    })?;
+
+    let expected = r#"Found 2 members
+
+Summary:
+  1 dependencies to consolidate
+  1 version conflicts resolved
+  1 conflicts could not resolve
+
+Will consolidate:
+  serde = "1.0.150" in: member1, member2
+
+Resolved conflicts (using Highest):
+  serde: 1.0.150, 1.1.0-alpha.1 → 1.0.150 (1 member moves)
+    downgrade: member2
+
+Could not resolve:
+  tokio (only prerelease versions):
+    2.0.0-rc.1 in: member1
+    2.0.0-rc.2 in: member2
+
+Updating workspace Cargo.toml...
+Consolidated 1 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    Ok(())
+}
+
+#[test]
+fn allows_prereleases_by_default() -> Result<()> {
+    let workspace = TestWorkspace::new("test_prerelease/before")?;
+
+    workspace.run(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolution_strategy: VersionResolutionStrategy::Highest,
+        prerelease_policy: PrereleasePolicy::Allow,
+        ..Default::default()
+    })?;
+
+    let manifest = std::fs::read_to_string(workspace.path.join("Cargo.toml"))?;
+    assert!(manifest.contains("serde = \"1.1.0-alpha.1\"\n"));
+
+    Ok(())
+}
+
+#[test]
+fn keeps_prereleases_on_another_major_out() -> Result<()> {
+    for policy in [PrereleasePolicy::PreferStable, PrereleasePolicy::Deny] {
+        let workspace = TestWorkspace::new("test_prerelease_major/before")?;
+
+        let output = workspace.run_captured(Config {
+            fix: true,
+            workspace_path: Some(workspace.path.clone()),
+            version_resolution_strategy: VersionResolutionStrategy::Highest,
+            prerelease_policy: policy,
+            ..Default::default()
+        })?;
+
+        let expected = r#"Found 2 members
+
+Summary:
+  1 dependencies to consolidate
+  1 conflicts could not resolve
+
+Will consolidate:
+  serde = "1.0" in: member1, member2
+
+Could not resolve:
+  clap (version resolution):
+    3.2 in: member1
+    4.0.0-rc.1 in: member2
+    note: '4.0.0-rc.1' is a prerelease, moving it to '3.2' would be breaking
+
+Updating workspace Cargo.toml...
+Consolidated 1 dependencies
+"#;
+
+        assert_eq!(
+            output, expected,
+            "\n=== Expected ===\n{}\n=== Got ===\n{}",
+            expected, output
+        );
+
+        let manifest = std::fs::read_to_string(workspace.path.join("member2/Cargo.toml"))?;
+        assert!(manifest.contains("clap = \"4.0.0-rc.1\"\n"));
+    }
+
+    Ok(())
+}
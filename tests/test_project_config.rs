@@ -0,0 +1,168 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{
+    Config, PrereleasePolicy, ProjectConfig, VersionFormat, VersionResolutionStrategy,
+    load_project_config, print_config,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use test_helpers::TestWorkspace;
+
+#[test]
+fn reads_metadata_and_config_file() -> Result<()> {
+    let workspace = TestWorkspace::new("test_project_config/before")?;
+
+    let project = load_project_config(Some(&workspace.path))?;
+
+    // workspace-deps.toml takes precedence over the workspace metadata
+    assert_eq!(project.exclude, Some(vec!["anyhow".to_string()]));
+    assert_eq!(project.min_members, Some(2));
+    assert_eq!(
+        project.version_resolution_strategy,
+        Some(VersionResolutionStrategy::Highest)
+    );
+    assert_eq!(project.prerelease_policy, Some(PrereleasePolicy::Deny));
+    assert_eq!(project.hoist_features, Some(true));
+    assert_eq!(project.process_dependencies, None);
+    assert_eq!(project.exact_pin_policy, None);
+
+    let overrides: Vec<_> = project
+        .version_resolution_overrides
+        .iter()
+        .map(|o| (o.pattern.as_str(), o.strategy))
+        .collect();
+    assert_eq!(
+        overrides,
+        [
+            ("openssl*", VersionResolutionStrategy::Fail),
+            ("libc", VersionResolutionStrategy::Lowest),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn prints_effective_config() -> Result<()> {
+    let workspace = TestWorkspace::new("test_project_config/before")?;
+
+    let captured = Rc::new(RefCell::new(String::new()));
+    let captured_clone = captured.clone();
+
+    // Set like the matching flags, which take precedence over the settings files
+    let flags = ProjectConfig {
+        process_dev_dependencies: Some(false),
+        exclude_members: Some(vec![glob::Pattern::new("vendor/*")?]),
+        version_resolution_overrides: vec!["rustls=fail".parse().unwrap()],
+        version_format: Some(VersionFormat::MajorMinor),
+        ..Default::default()
+    };
+    let config = Config {
+        workspace_path: Some(workspace.path.clone()),
+        output_callback: Some(Box::new(move |s| {
+            captured_clone.borrow_mut().push_str(s);
+        })),
+        ..Default::default()
+    }
+    .with_project_config(flags.or(load_project_config(Some(&workspace.path))?));
+    print_config(&config)?;
+
+    let output = captured.borrow().clone();
+
+    let expected = r#"no-dependencies = false
+no-dev-dependencies = true
+no-build-dependencies = false
+exclude = ["anyhow"]
+exclude-members = ["vendor/*"]
+min-members = 2
+version-resolution = "highest"
+prerelease = "deny"
+exact-pins = "conflict"
+msrv = false
+deny-breaking = false
+partial = false
+default-features-resolution = "skip"
+version-format = "major.minor"
+path-dependencies = false
+hoist-features = true
+prune-unused = false
+demote = false
+package = false
+lints = false
+
+[version-resolution-override]
+rustls = "fail"
+"openssl*" = "fail"
+libc = "lowest"
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    Ok(())
+}
+
+#[test]
+fn flags_turn_off_settings_the_files_enable() -> Result<()> {
+    let workspace = TestWorkspace::new("test_project_config/before")?;
+
+    let flags = ProjectConfig {
+        min_members: Some(3),
+        hoist_features: Some(false),
+        ..Default::default()
+    };
+    let config = Config::default()
+        .with_project_config(flags.or(load_project_config(Some(&workspace.path))?));
+
+    assert_eq!(config.min_members, 3);
+    assert!(!config.hoist_features);
+    // Settings the flags leave alone still come from the files
+    assert_eq!(config.prerelease_policy, PrereleasePolicy::Deny);
+
+    Ok(())
+}
+
+#[test]
+fn reports_unknown_metadata_key_with_location() -> Result<()> {
+    let workspace = TestWorkspace::new("test_project_config/before")?;
+
+    let manifest_path = workspace.path.join("Cargo.toml");
+    let manifest = std::fs::read_to_string(&manifest_path)?;
+    std::fs::write(
+        &manifest_path,
+        manifest.replace("min-members = 3", "min-member = 3"),
+    )?;
+
+    let err = load_project_config(Some(&workspace.path)).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        format!("{}:7:1: unknown key 'min-member'", manifest_path.display())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reports_invalid_value_with_location() -> Result<()> {
+    let workspace = TestWorkspace::new("test_project_config/before")?;
+
+    let config_path = workspace.path.join("workspace-deps.toml");
+    std::fs::write(&config_path, "min-members = 2\nprerelease = \"never\"\n")?;
+
+    let err = load_project_config(Some(&workspace.path)).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "{}:2:14: Invalid value 'never' for 'prerelease' (expected one of: allow, prefer-stable, deny)",
+            config_path.display()
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{CheckFailure, Config};
+use test_helpers::TestWorkspace;
+
+#[test]
+fn removes_unused_entries_and_their_comments() -> Result<()> {
+    let workspace = TestWorkspace::new("test_prune_unused/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        prune_unused: true,
+        ..Default::default()
+    })?;
+
+    let expected = r#"Found 2 members
+
+Summary:
+  1 dependencies to consolidate
+  2 unused workspace dependencies
+
+Will consolidate:
+  anyhow = "1.0" in: member1, member2
+
+Unused workspace dependencies:
+  regex
+  tempfile
+
+Updating workspace Cargo.toml...
+Consolidated 1 dependencies
+Removed 2 unused workspace dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_prune_unused/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn check_fails_on_unused_entries() -> Result<()> {
+    let workspace = TestWorkspace::new("test_prune_unused/before")?;
+
+    // Nothing left to consolidate, so only the unused entries fail the check
+    let err = workspace
+        .run(Config {
+            exclude: vec!["anyhow".to_string()],
+            fix: true,
+            workspace_path: Some(workspace.path.clone()),
+            check: true,
+            prune_unused: true,
+            ..Default::default()
+        })
+        .unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<CheckFailure>(),
+        Some(CheckFailure::UnusedDependencies(2))
+    ));
+
+    workspace.assert_matches("test_prune_unused/before")?;
+
+    Ok(())
+}
+
+#[test]
+fn keeps_entries_used_by_excluded_members() -> Result<()> {
+    let workspace = TestWorkspace::new("test_prune_unused/before")?;
+
+    workspace.run(Config {
+        exclude_members: vec![glob::Pattern::new("member1")?],
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        prune_unused: true,
+        ..Default::default()
+    })?;
+
+    // log is only inherited by member1, which is left out but still uses it
+    let root = std::fs::read_to_string(workspace.path.join("Cargo.toml"))?;
+    assert!(root.contains("log = \"0.4\" # kept for member1\n"));
+    assert!(!root.contains("regex"));
+
+    Ok(())
+}
+
+#[test]
+fn prunes_with_every_dependency_section_turned_off() -> Result<()> {
+    let workspace = TestWorkspace::new("test_prune_unused/before")?;
+
+    workspace.run(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        process_dependencies: false,
+        process_dev_dependencies: false,
+        process_build_dependencies: false,
+        prune_unused: true,
+        output_callback: Some(Box::new(|_| {})),
+        ..Default::default()
+    })?;
+
+    // Nothing is consolidated, but the unused entries still go
+    workspace.assert_matches("test_prune_unused/after_only")?;
+
+    Ok(())
+}
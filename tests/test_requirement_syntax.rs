@@ -0,0 +1,83 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{Config, ExactPinPolicy, OutputFormat};
+use test_helpers::TestWorkspace;
+
+#[test]
+fn resolves_cargo_requirement_forms() -> Result<()> {
+    let workspace = TestWorkspace::new("test_requirement_syntax/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        exact_pin_policy: ExactPinPolicy::Win,
+        ..Default::default()
+    })?;
+
+    let expected = r#"Found 2 members
+
+Summary:
+  3 dependencies to consolidate
+  3 version conflicts resolved
+  1 conflicts could not resolve
+
+Will consolidate:
+  log = "0.4.20" in: member1, member2
+  regex = "=1.10.2" in: member1, member2 (pinned by member1)
+  serde = "1.0.150" in: member1, member2
+
+Resolved conflicts (using HighestCompatible):
+  log: *, 0.4.20 → 0.4.20 (1 member moves)
+    upgrade: member1
+  regex: 1.10, =1.10.2 → =1.10.2 (1 member moves)
+    upgrade: member2
+  serde: 1.0.150, (>=1.0.100, <2) → 1.0.150 (1 member moves)
+    upgrade: member1
+
+Could not resolve:
+  tokio (version resolution):
+    1.4 in: member2
+    ~1.2 in: member1
+    note: No version satisfies all requirements: '1.4' (member2) and '~1.2' (member1) do not overlap
+
+Updating workspace Cargo.toml...
+Consolidated 3 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_requirement_syntax/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn reports_diagnostics_in_json() -> Result<()> {
+    let workspace = TestWorkspace::new("test_requirement_syntax/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        exact_pin_policy: ExactPinPolicy::Win,
+        output_format: OutputFormat::Json,
+        ..Default::default()
+    })?;
+
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+    let tokio = &json["conflicts"][0];
+
+    assert_eq!(tokio["name"], "tokio");
+    assert_eq!(
+        tokio["diagnostics"],
+        serde_json::json!([
+            "No version satisfies all requirements: '1.4' (member2) and '~1.2' (member1) do not overlap"
+        ])
+    );
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{
+    Config, VersionResolutionOverride, VersionResolutionStrategy, load_project_config,
+};
+use test_helpers::TestWorkspace;
+
+#[test]
+fn applies_overrides_from_cli_and_config_file() -> Result<()> {
+    let workspace = TestWorkspace::new("test_resolution_overrides/before")?;
+
+    // Overrides given like flags come before those in workspace-deps.toml
+    let output = workspace.run_captured(
+        Config {
+            fix: true,
+            workspace_path: Some(workspace.path.clone()),
+            version_resolution_overrides: vec!["rustls=fail".parse().unwrap()],
+            ..Default::default()
+        }
+        .with_project_config(load_project_config(Some(&workspace.path))?),
+    )?;
+
+    let expected = r#"Found 2 members
+
+Summary:
+  2 dependencies to consolidate
+  2 version conflicts resolved
+  2 conflicts could not resolve
+
+Will consolidate:
+  anyhow = "1.0.80" in: member1, member2
+  libc = "0.2.100" in: member1, member2
+
+Resolved conflicts (using HighestCompatible):
+  anyhow: 1.0.75, 1.0.80 → 1.0.80 (1 member moves)
+    upgrade: member1
+  libc: 0.2.100, 0.2.150 → 0.2.100 (using Lowest, 1 member moves)
+    downgrade: member2
+
+Could not resolve:
+  openssl-sys (version resolution):
+    0.9.100 in: member2
+    0.9.90 in: member1
+  rustls (version resolution):
+    0.21.10 in: member1
+    0.21.12 in: member2
+
+Updating workspace Cargo.toml...
+Consolidated 2 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_resolution_overrides/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn cli_overrides_take_precedence_over_config_file() -> Result<()> {
+    let workspace = TestWorkspace::new("test_resolution_overrides/before")?;
+
+    workspace.run(
+        Config {
+            fix: true,
+            workspace_path: Some(workspace.path.clone()),
+            version_resolution_overrides: vec![
+                "libc=highest-compatible".parse().unwrap(),
+                "openssl-*=highest-compatible".parse().unwrap(),
+            ],
+            ..Default::default()
+        }
+        .with_project_config(load_project_config(Some(&workspace.path))?),
+    )?;
+
+    let root = std::fs::read_to_string(workspace.path.join("Cargo.toml"))?;
+    assert!(root.contains("libc = \"0.2.150\""), "{}", root);
+    assert!(root.contains("openssl-sys = \"0.9.100\""), "{}", root);
+    assert!(root.contains("rustls = \"0.21.12\""), "{}", root);
+
+    Ok(())
+}
+
+#[test]
+fn rejects_invalid_config_file_strategy() -> Result<()> {
+    let workspace = TestWorkspace::new("test_resolution_overrides/before")?;
+    std::fs::write(
+        workspace.path.join("workspace-deps.toml"),
+        "[version-resolution]\nlibc = \"newest\"\n",
+    )?;
+
+    let Err(err) = load_project_config(Some(&workspace.path)) else {
+        panic!("the invalid strategy was accepted");
+    };
+    let message = err.to_string();
+    assert!(message.contains("workspace-deps.toml"), "{}", message);
+    assert!(message.contains("'newest'"), "{}", message);
+
+    workspace.assert_matches("test_resolution_overrides/before")?;
+
+    Ok(())
+}
+
+#[test]
+fn parses_override_syntax() {
+    let parsed: VersionResolutionOverride = "openssl*=fail".parse().unwrap();
+    assert!(parsed.pattern.matches("openssl-sys"));
+    assert_eq!(parsed.strategy, VersionResolutionStrategy::Fail);
+
+    assert!("openssl".parse::<VersionResolutionOverride>().is_err());
+    assert!(
+        "openssl=newest"
+            .parse::<VersionResolutionOverride>()
+            .is_err()
+    );
+}
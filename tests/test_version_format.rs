@@ -0,0 +1,87 @@
+mod test_helpers;
+
+use anyhow::Result;
+use cargo_workspace_deps::{Config, VersionFormat};
+use test_helpers::TestWorkspace;
+
+#[test]
+fn writes_full_versions() -> Result<()> {
+    let workspace = TestWorkspace::new("test_version_format/before")?;
+
+    let output = workspace.run_captured(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_format: VersionFormat::Full,
+        ..Default::default()
+    })?;
+
+    let expected = r#"Found 2 members
+
+Summary:
+  4 dependencies to consolidate
+  1 version conflicts resolved
+
+Will consolidate:
+  anyhow = "1.0.80" in: member1, member2
+  log = "0.4.0" in: member1, member2
+  serde = "1.0.0" in: member1, member2
+  tokio = "~1.2" in: member1, member2
+
+Resolved conflicts (using HighestCompatible):
+  anyhow: 1.0, 1.0.80 → 1.0.80 (1 member moves)
+    upgrade: member1
+
+Updating workspace Cargo.toml...
+Consolidated 4 dependencies
+"#;
+
+    assert_eq!(
+        output, expected,
+        "\n=== Expected ===\n{}\n=== Got ===\n{}",
+        expected, output
+    );
+
+    workspace.assert_matches("test_version_format/after")?;
+
+    Ok(())
+}
+
+#[test]
+fn keeps_written_precision_by_default() -> Result<()> {
+    let workspace = TestWorkspace::new("test_version_format/before")?;
+
+    workspace.run(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_format: VersionFormat::AsWritten,
+        output_callback: Some(Box::new(|_| {})),
+        ..Default::default()
+    })?;
+
+    let manifest = std::fs::read_to_string(workspace.path.join("Cargo.toml"))?;
+    assert!(manifest.contains("anyhow = \"1.0.80\"\n"));
+    assert!(manifest.contains("log = \"0.4\"\n"));
+    assert!(manifest.contains("serde = \"1\"\n"));
+    assert!(manifest.contains("tokio = \"~1.2\"\n"));
+
+    Ok(())
+}
+
+#[test]
+fn keeps_zero_major_components() -> Result<()> {
+    let workspace = TestWorkspace::new("test_version_format/before")?;
+
+    workspace.run(Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_format: VersionFormat::Major,
+        output_callback: Some(Box::new(|_| {})),
+        ..Default::default()
+    })?;
+
+    let manifest = std::fs::read_to_string(workspace.path.join("Cargo.toml"))?;
+    assert!(manifest.contains("anyhow = \"1\"\n"));
+    assert!(manifest.contains("log = \"0.4\"\n"));
+
+    Ok(())
+}
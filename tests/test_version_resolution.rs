@@ -1,7 +1,7 @@
 mod test_helpers;
 
 use anyhow::Result;
-use cargo_workspace_deps::{Config, OutputFormat, VersionResolutionStrategy};
+use cargo_workspace_deps::{Config, VersionResolutionStrategy};
 use test_helpers::TestWorkspace;
 
 #[test]
@@ -10,17 +10,9 @@ fn highest_strategy_uses_highest_version() -> Result<()> {
 
     workspace.run(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
-        check: false,
         version_resolution_strategy: VersionResolutionStrategy::Highest,
-        output_format: OutputFormat::Text,
-        output_callback: None,
+        ..Default::default()
     })?;
 
     workspace.assert_matches("test_version_resolution/after_highest")?;
@@ -34,17 +26,9 @@ fn lowest_strategy_uses_lowest_version() -> Result<()> {
 
     workspace.run(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
-        check: false,
         version_resolution_strategy: VersionResolutionStrategy::Lowest,
-        output_format: OutputFormat::Text,
-        output_callback: None,
+        ..Default::default()
     })?;
 
     workspace.assert_matches("test_version_resolution/after_lowest")?;
@@ -58,20 +42,75 @@ fn highest_compatible_resolves_to_compatible_version() -> Result<()> {
 
     workspace.run(Config {
         fix: true,
-        process_dependencies: true,
-        process_dev_dependencies: true,
-        process_build_dependencies: true,
         workspace_path: Some(workspace.path.clone()),
-        exclude: Vec::new(),
-        min_members: 2,
-        exclude_members: Vec::new(),
-        check: false,
-        version_resolution_strategy: VersionResolutionStrategy::HighestCompatible,
-        output_format: OutputFormat::Text,
-        output_callback: None,
+        ..Default::default()
     })?;
 
     workspace.assert_matches("test_version_resolution/after_highest_compatible")?;
 
     Ok(())
 }
+
+/// Write a Cargo.lock locking the given serde versions
+fn write_lockfile(workspace: &TestWorkspace, serde_versions: &[&str]) -> Result<()> {
+    let mut lockfile = String::from("version = 3\n");
+    for member in ["member1", "member2", "member3"] {
+        lockfile.push_str(&format!(
+            "\n[[package]]\nname = \"{}\"\nversion = \"0.1.0\"\ndependencies = [\n \"serde\",\n]\n",
+            member
+        ));
+    }
+    for version in serde_versions {
+        lockfile.push_str(&format!(
+            "\n[[package]]\nname = \"serde\"\nversion = \"{}\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+            version
+        ));
+    }
+    std::fs::write(workspace.path.join("Cargo.lock"), lockfile)?;
+    Ok(())
+}
+
+fn lockfile_config(workspace: &TestWorkspace) -> Config {
+    Config {
+        fix: true,
+        workspace_path: Some(workspace.path.clone()),
+        version_resolution_strategy: VersionResolutionStrategy::Lockfile,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn lockfile_strategy_uses_locked_version() -> Result<()> {
+    let workspace = TestWorkspace::new("test_version_resolution/before")?;
+    write_lockfile(&workspace, &["1.0.197"])?;
+
+    workspace.run(lockfile_config(&workspace))?;
+
+    workspace.assert_matches("test_version_resolution/after_lockfile")?;
+
+    Ok(())
+}
+
+#[test]
+fn lockfile_strategy_falls_back_with_several_locked_versions() -> Result<()> {
+    let workspace = TestWorkspace::new("test_version_resolution/before")?;
+    write_lockfile(&workspace, &["0.9.15", "1.0.197"])?;
+
+    workspace.run(lockfile_config(&workspace))?;
+
+    workspace.assert_matches("test_version_resolution/after_highest_compatible")?;
+
+    Ok(())
+}
+
+#[test]
+fn lockfile_strategy_requires_lockfile() -> Result<()> {
+    let workspace = TestWorkspace::new("test_version_resolution/before")?;
+
+    let err = workspace.run(lockfile_config(&workspace)).unwrap_err();
+    assert!(err.to_string().contains("Cargo.lock"));
+
+    workspace.assert_matches("test_version_resolution/before")?;
+
+    Ok(())
+}